@@ -65,26 +65,58 @@ const BASE_DIR_VARS: &[&str] = &[
 const COMMANDS: &[&str] = &[
     "mkdir",
     "create",
+    "create_temp_file",
+    "create_temp_dir",
     "copy_file",
+    "symlink",
+    "hard_link",
+    "copy",
     "remove",
+    "trash",
+    "list_trash",
+    "restore_from_trash",
     "rename",
     "truncate",
     "ftruncate",
     "write",
+    "create_write_stream",
+    "write_stream",
+    "finish_write_stream",
     "write_file",
     "write_text_file",
     "read_dir",
+    "walk_dir",
     "read_file",
     "read",
+    "read_stream",
+    "read_stream_ack",
     "open",
     "read_text_file",
     "read_text_file_lines",
     "read_text_file_lines_next",
     "seek",
+    "lock",
+    "try_lock",
+    "unlock",
     "stat",
     "lstat",
     "fstat",
+    "read_link",
+    "canonicalize",
+    "dir_size",
+    "statfs",
+    "chmod",
+    "chown",
+    "utimes",
+    "get_xattr",
+    "set_xattr",
+    "list_xattr",
+    "remove_xattr",
     "exists",
+    "hash",
+    "verify",
+    "compress",
+    "extract",
     "watch",
     "unwatch",
 ];
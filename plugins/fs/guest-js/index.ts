@@ -220,6 +220,11 @@ interface UnparsedFileInfo {
   blksize: number | null
   blocks: number | null
 }
+
+interface UnparsedWalkEntry extends Omit<WalkEntry, 'info'> {
+  info?: UnparsedFileInfo
+}
+
 function parseFileInfo(r: UnparsedFileInfo): FileInfo {
   return {
     isFile: r.isFile,
@@ -262,6 +267,88 @@ function fromBytes(buffer: FixedSizeArray<number, 8>): number {
   return x
 }
 
+/**
+ * @since 2.1.0
+ */
+interface ReadStreamOptions {
+  /** Offset of the first byte to read. Defaults to the current position of the file. */
+  start?: number
+  /** Offset of the byte after the last byte to read. Defaults to the end of the file. */
+  end?: number
+  /** Maximum size of a chunk in bytes. Defaults to 1 MiB. */
+  chunkSize?: number
+  /** Number of chunks read ahead of the consumer. Defaults to `4`. */
+  highWaterMark?: number
+}
+
+async function* readStreamChunks(
+  rid: number,
+  options?: ReadStreamOptions
+): AsyncGenerator<Uint8Array, void> {
+  const chunks: Uint8Array[] = []
+  const state: {
+    sent: number | null
+    failed: boolean
+    error?: unknown
+    wake?: () => void
+  } = { sent: null, failed: false }
+
+  const onChunk = new Channel<ArrayBuffer>()
+  onChunk.onmessage = (chunk) => {
+    chunks.push(new Uint8Array(chunk))
+    state.wake?.()
+  }
+
+  invoke<number>('plugin:fs|read_stream', { rid, options, onChunk })
+    .then((sent) => {
+      state.sent = sent
+    })
+    .catch((e: unknown) => {
+      state.failed = true
+      state.error = e
+    })
+    .finally(() => state.wake?.())
+
+  let received = 0
+  let done = false
+  try {
+    for (;;) {
+      const chunk = chunks.shift()
+      if (chunk) {
+        received += chunk.byteLength
+        yield chunk
+        // only acknowledge the chunk once the consumer asks for the next one
+        await invoke('plugin:fs|read_stream_ack', { rid })
+      } else if (state.failed) {
+        done = true
+        throw state.error
+      } else if (state.sent !== null && received >= state.sent) {
+        // chunks can still arrive after the command resolved
+        done = true
+        return
+      } else {
+        await new Promise<void>((resolve) => {
+          state.wake = resolve
+        })
+        state.wake = undefined
+      }
+    }
+  } finally {
+    if (!done) {
+      // the consumer stopped early
+      await invoke('plugin:fs|read_stream_ack', { rid, cancel: true })
+    }
+  }
+}
+
+/**
+ * Mode of a file lock, a `'shared'` lock can be held through several handles
+ * at once while an `'exclusive'` lock cannot be held with any other lock.
+ *
+ * @since 2.1.0
+ */
+type LockMode = 'shared' | 'exclusive'
+
 /**
  *  The Tauri abstraction for reading and writing files.
  *
@@ -441,6 +528,85 @@ class FileHandle extends Resource {
       data
     })
   }
+
+  /**
+   * Reads the file in chunks without loading it into memory.
+   * Only `highWaterMark` chunks are read ahead of the consumer.
+   *
+   * @example
+   * ```typescript
+   * import { open, BaseDirectory } from '@tauri-apps/plugin-fs';
+   * const file = await open('video.mp4', { read: true, baseDir: BaseDirectory.Video });
+   * for await (const chunk of file.readStream({ start: 1024 })) {
+   *   console.log(chunk.byteLength);
+   * }
+   * await file.close();
+   * ```
+   *
+   * @since 2.1.0
+   */
+  readStream(options?: ReadStreamOptions): AsyncGenerator<Uint8Array, void> {
+    return readStreamChunks(this.rid, options)
+  }
+
+  /**
+   * Takes an advisory lock on the whole file, waiting until conflicting locks
+   * held through other handles are released. A lock already held through this
+   * handle is released first. The lock is released when the file is closed.
+   *
+   * On Unix the lock only excludes other handles that lock the file too.
+   *
+   * @example
+   * ```typescript
+   * import { open, BaseDirectory } from '@tauri-apps/plugin-fs';
+   * const file = await open('db.json', { read: true, write: true, baseDir: BaseDirectory.AppData });
+   * await file.lock('exclusive');
+   * await file.write(new TextEncoder().encode('{}'));
+   * await file.unlock();
+   * await file.close();
+   * ```
+   *
+   * @since 2.1.0
+   */
+  async lock(mode: LockMode = 'exclusive'): Promise<void> {
+    await invoke('plugin:fs|lock', {
+      rid: this.rid,
+      mode
+    })
+  }
+
+  /**
+   * Like {@linkcode FileHandle.lock} but resolves to `false` instead of
+   * waiting if the file is locked through another handle.
+   *
+   * @example
+   * ```typescript
+   * import { open, BaseDirectory } from '@tauri-apps/plugin-fs';
+   * const file = await open('db.json', { read: true, baseDir: BaseDirectory.AppData });
+   * if (!(await file.tryLock('shared'))) {
+   *   console.log('the file is being written');
+   * }
+   * ```
+   *
+   * @since 2.1.0
+   */
+  async tryLock(mode: LockMode = 'exclusive'): Promise<boolean> {
+    return await invoke('plugin:fs|try_lock', {
+      rid: this.rid,
+      mode
+    })
+  }
+
+  /**
+   * Releases the lock held through this handle, if any.
+   *
+   * @since 2.1.0
+   */
+  async unlock(): Promise<void> {
+    await invoke('plugin:fs|unlock', {
+      rid: this.rid
+    })
+  }
 }
 
 /**
@@ -566,6 +732,79 @@ async function open(
   return new FileHandle(rid)
 }
 
+/**
+ * @since 2.1.0
+ */
+interface TempOptions {
+  /** Start of the name, before a random part. */
+  prefix?: string
+  /** End of the name, after a random part, e.g. an extension like `'.png'`. */
+  suffix?: string
+}
+
+/**
+ * A temporary file or directory created with {@linkcode createTempFile} or
+ * {@linkcode createTempDir}.
+ *
+ * Its path is allowed in the scope of the current webview until it is closed,
+ * the webview is destroyed or the app exits, at which point it is deleted.
+ *
+ * @since 2.1.0
+ */
+class TempPath extends Resource {
+  /** Absolute path of the file or directory. */
+  readonly path: string
+
+  constructor(rid: number, path: string) {
+    super(rid)
+    this.path = path
+  }
+}
+
+/**
+ * Creates an empty file in the temporary directory of the system.
+ * @example
+ * ```typescript
+ * import { createTempFile, writeFile } from '@tauri-apps/plugin-fs';
+ * const temp = await createTempFile({ suffix: '.png' });
+ * await writeFile(temp.path, new Uint8Array([137, 80, 78, 71]));
+ * // deletes the file
+ * await temp.close();
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function createTempFile(options?: TempOptions): Promise<TempPath> {
+  const { rid, path } = await invoke<{ rid: number; path: string }>(
+    'plugin:fs|create_temp_file',
+    { options }
+  )
+
+  return new TempPath(rid, path)
+}
+
+/**
+ * Creates an empty directory in the temporary directory of the system.
+ * @example
+ * ```typescript
+ * import { createTempDir, writeTextFile } from '@tauri-apps/plugin-fs';
+ * const temp = await createTempDir({ prefix: 'export-' });
+ * await writeTextFile(`${temp.path}/notes.txt`, 'draft');
+ * // deletes the directory and its content
+ * await temp.close();
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function createTempDir(options?: TempOptions): Promise<TempPath> {
+  const { rid, path } = await invoke<{ rid: number; path: string }>(
+    'plugin:fs|create_temp_dir',
+    { options }
+  )
+
+  return new TempPath(rid, path)
+}
+
 /**
  * @since 2.0.0
  */
@@ -605,6 +844,91 @@ async function copyFile(
   })
 }
 
+/**
+ * Progress of a {@linkcode copy} operation.
+ *
+ * @since 2.1.0
+ */
+interface CopyProgress {
+  /** Number of files and symlinks processed so far, including skipped ones. */
+  filesDone: number
+  /** Total number of files and symlinks to copy. */
+  filesTotal: number
+  /** Number of bytes processed so far. */
+  bytesDone: number
+  /** Total number of bytes to copy. */
+  bytesTotal: number
+  /** The source path that was processed last. */
+  currentPath: string
+}
+
+/**
+ * @since 2.1.0
+ */
+interface CopyOptions {
+  /** Base directory for `fromPath`. */
+  fromPathBaseDir?: BaseDirectory
+  /** Base directory for `toPath`. */
+  toPathBaseDir?: BaseDirectory
+  /**
+   * What to do when a file already exists at the destination, defaults to `'overwrite'`.
+   * Existing directories are always merged.
+   */
+  onConflict?: 'overwrite' | 'skip' | 'error'
+  /** Copy the access and modification times. Defaults to `false`. */
+  preserveTimestamps?: boolean
+  /** Copy the permissions. Defaults to `true`. */
+  preservePermissions?: boolean
+  /**
+   * Copy the files symlinks point to instead of the symlinks themselves. Defaults to `false`.
+   * The targets of copied symlinks must also be allowed by the scope.
+   */
+  followSymlinks?: boolean
+  /** Called after each file is copied. */
+  onProgress?: (progress: CopyProgress) => void
+}
+
+/**
+ * Copies a file or a whole directory tree to the specified path.
+ * @example
+ * ```typescript
+ * import { copy, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await copy('project', 'project-backup', {
+ *   fromPathBaseDir: BaseDirectory.AppData,
+ *   toPathBaseDir: BaseDirectory.AppData,
+ *   onConflict: 'skip',
+ *   onProgress: ({ filesDone, filesTotal }) => console.log(`${filesDone}/${filesTotal}`)
+ * });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function copy(
+  fromPath: string | URL,
+  toPath: string | URL,
+  options?: CopyOptions
+): Promise<void> {
+  if (
+    (fromPath instanceof URL && fromPath.protocol !== 'file:') ||
+    (toPath instanceof URL && toPath.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const { onProgress, ...opts } = options ?? {}
+  const channel = new Channel<CopyProgress>()
+  if (onProgress) {
+    channel.onmessage = onProgress
+  }
+
+  await invoke('plugin:fs|copy', {
+    fromPath: fromPath instanceof URL ? fromPath.toString() : fromPath,
+    toPath: toPath instanceof URL ? toPath.toString() : toPath,
+    options: opts,
+    onProgress: channel
+  })
+}
+
 /**
  * @since 2.0.0
  */
@@ -705,6 +1029,101 @@ async function readDir(
   })
 }
 
+/**
+ * @since 2.1.0
+ */
+interface WalkDirOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+  /** Maximum depth to descend to, `1` only lists the direct children of `path`. Unlimited by default. */
+  maxDepth?: number
+  /**
+   * Only report entries whose path relative to `path` matches one of these glob patterns.
+   * Directories that do not match are still traversed.
+   */
+  include?: string[]
+  /** Skip entries whose path relative to `path` matches one of these glob patterns, without traversing them. */
+  exclude?: string[]
+  /**
+   * Whether to list entries whose name starts with a dot.
+   * Defaults to the inverse of the `requireLiteralLeadingDot` plugin configuration.
+   */
+  includeHidden?: boolean
+  /** Include the {@linkcode FileInfo} of each entry. Defaults to `false`. */
+  stat?: boolean
+  /** Sorts the entries of each directory. */
+  sort?: 'name' | 'size' | 'modified'
+  /** Maximum number of entries delivered to the callback at once. Defaults to `256`. */
+  chunkSize?: number
+}
+
+/**
+ * An entry found by {@linkcode walkDir}.
+ *
+ * @since 2.1.0
+ */
+interface WalkEntry {
+  /** The absolute path of the entry. */
+  path: string
+  /** The name of the entry (file name with extension or directory name). */
+  name: string
+  /** The depth of the entry, `1` for the direct children of the walked directory. */
+  depth: number
+  /** Specifies whether this entry is a directory or not. */
+  isDirectory: boolean
+  /** Specifies whether this entry is a file or not. */
+  isFile: boolean
+  /** Specifies whether this entry is a symlink or not. Symlinks are never followed. */
+  isSymlink: boolean
+  /** The metadata of the entry if {@linkcode WalkDirOptions.stat} is set. */
+  info?: FileInfo
+}
+
+/**
+ * Recursively walks the directory given by path, calling `cb` with chunks of entries as they are found.
+ * Entries outside of the scope are skipped.
+ *
+ * Resolves to the total number of entries.
+ * @example
+ * ```typescript
+ * import { walkDir, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const sources = [];
+ * await walkDir('project', (entries) => sources.push(...entries), {
+ *   baseDir: BaseDirectory.AppData,
+ *   include: ['src/*.ts'],
+ *   exclude: ['node_modules'],
+ *   sort: 'name'
+ * });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function walkDir(
+  path: string | URL,
+  cb: (entries: WalkEntry[]) => void,
+  options?: WalkDirOptions
+): Promise<number> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const onEntries = new Channel<UnparsedWalkEntry[]>()
+  onEntries.onmessage = (entries) => {
+    cb(
+      entries.map((entry) => ({
+        ...entry,
+        info: entry.info ? parseFileInfo(entry.info) : undefined
+      }))
+    )
+  }
+
+  return await invoke('plugin:fs|walk_dir', {
+    path: path instanceof URL ? path.toString() : path,
+    options,
+    onEntries
+  })
+}
+
 /**
  * @since 2.0.0
  */
@@ -740,6 +1159,41 @@ async function readFile(
   return arr instanceof ArrayBuffer ? new Uint8Array(arr) : Uint8Array.from(arr)
 }
 
+/**
+ * @since 2.1.0
+ */
+interface ReadFileStreamOptions extends ReadStreamOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Reads a file in chunks without loading it into memory.
+ * The file is closed once the stream is consumed or the consumer stops early.
+ *
+ * @example
+ * ```typescript
+ * import { readStream, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * for await (const chunk of readStream('video.mp4', { baseDir: BaseDirectory.Video })) {
+ *   console.log(chunk.byteLength);
+ * }
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function* readStream(
+  path: string | URL,
+  options?: ReadFileStreamOptions
+): AsyncGenerator<Uint8Array, void> {
+  const { baseDir, ...streamOptions } = options ?? {}
+  const file = await open(path, { read: true, baseDir })
+  try {
+    yield* file.readStream(streamOptions)
+  } finally {
+    await file.close()
+  }
+}
+
 /**
  * Reads and returns the entire contents of a file as UTF-8 string.
  * @example
@@ -855,6 +1309,99 @@ async function remove(
   })
 }
 
+/**
+ * @since 2.1.0
+ */
+interface TrashOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Moves the named file or directory to the trash of the current user.
+ * On Linux and the BSDs this follows the freedesktop.org Trash specification.
+ * @example
+ * ```typescript
+ * import { trash, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await trash('users/file.txt', { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function trash(
+  path: string | URL,
+  options?: TrashOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|trash', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * An item of the trash, returned by {@linkcode listTrash}.
+ *
+ * @since 2.1.0
+ */
+interface TrashItem {
+  /** Identifies the item to {@linkcode restoreFromTrash}. */
+  id: string
+  /** The path the item was deleted from. */
+  originalPath: string
+  /** When the item was moved to the trash, if known. */
+  deletedAt: Date | null
+  isDirectory: boolean
+}
+
+interface UnparsedTrashItem {
+  id: string
+  originalPath: string
+  deletedAt: number | null
+  isDirectory: boolean
+}
+
+/**
+ * Lists the items of the trash that were deleted from a path allowed by the scope.
+ *
+ * Not supported on macOS and mobile.
+ * @example
+ * ```typescript
+ * import { listTrash } from '@tauri-apps/plugin-fs';
+ * const items = await listTrash();
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function listTrash(): Promise<TrashItem[]> {
+  const items = await invoke<UnparsedTrashItem[]>('plugin:fs|list_trash')
+  return items.map((item) => ({
+    ...item,
+    deletedAt: item.deletedAt !== null ? new Date(item.deletedAt) : null
+  }))
+}
+
+/**
+ * Moves an item of the trash back to its original path and resolves to that path.
+ * Rejects if the original path already exists.
+ *
+ * Not supported on macOS and mobile.
+ * @example
+ * ```typescript
+ * import { listTrash, restoreFromTrash } from '@tauri-apps/plugin-fs';
+ * const [item] = await listTrash();
+ * await restoreFromTrash(item.id);
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function restoreFromTrash(id: string): Promise<string> {
+  return await invoke('plugin:fs|restore_from_trash', { id })
+}
+
 /**
  * @since 2.0.0
  */
@@ -959,22 +1506,490 @@ async function lstat(
 }
 
 /**
- * @since 2.0.0
+ * @since 2.1.0
  */
-interface TruncateOptions {
+interface SymlinkOptions {
+  /**
+   * Base directory for `target`. When set, the link points to the absolute
+   * path `target` resolves to.
+   */
+  targetBaseDir?: BaseDirectory
   /** Base directory for `path`. */
-  baseDir?: BaseDirectory
+  pathBaseDir?: BaseDirectory
 }
 
 /**
- * Truncates or extends the specified file, to reach the specified `len`.
- * If `len` is `0` or not specified, then the entire file contents are truncated.
- *
+ * Creates a symlink at `path` pointing to `target`. A relative `target` is
+ * relative to the directory of the link. Rejects if the path the link points
+ * to is outside of the scope.
  * @example
  * ```typescript
- * import { truncate, readTextFile, writeTextFile, BaseDirectory } from '@tauri-apps/plugin-fs';
- * // truncate the entire file
- * await truncate("my_file.txt", 0, { baseDir: BaseDirectory.AppLocalData });
+ * import { symlink, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await symlink('releases/1.2.0', 'current', { pathBaseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function symlink(
+  target: string | URL,
+  path: string | URL,
+  options?: SymlinkOptions
+): Promise<void> {
+  if (
+    (target instanceof URL && target.protocol !== 'file:') ||
+    (path instanceof URL && path.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|symlink', {
+    target: target instanceof URL ? target.toString() : target,
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * @since 2.1.0
+ */
+interface HardLinkOptions {
+  /** Base directory for `fromPath`. */
+  fromPathBaseDir?: BaseDirectory
+  /** Base directory for `toPath`. */
+  toPathBaseDir?: BaseDirectory
+}
+
+/**
+ * Creates a hard link at `toPath` to the existing file at `fromPath`.
+ * @example
+ * ```typescript
+ * import { hardLink, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await hardLink('cache/blob', 'exports/photo.jpg', { fromPathBaseDir: BaseDirectory.AppCache, toPathBaseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function hardLink(
+  fromPath: string | URL,
+  toPath: string | URL,
+  options?: HardLinkOptions
+): Promise<void> {
+  if (
+    (fromPath instanceof URL && fromPath.protocol !== 'file:') ||
+    (toPath instanceof URL && toPath.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|hard_link', {
+    fromPath: fromPath instanceof URL ? fromPath.toString() : fromPath,
+    toPath: toPath instanceof URL ? toPath.toString() : toPath,
+    options
+  })
+}
+
+/**
+ * @since 2.1.0
+ */
+interface ReadLinkOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Resolves to the target of the symlink at `path`, as it is stored in the link.
+ * @example
+ * ```typescript
+ * import { readLink, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const target = await readLink('current', { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function readLink(
+  path: string | URL,
+  options?: ReadLinkOptions
+): Promise<string> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|read_link', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * @since 2.1.0
+ */
+interface CanonicalizeOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Resolves to the absolute path of `path` with all symlinks resolved.
+ * Rejects if that path is outside of the scope.
+ * @example
+ * ```typescript
+ * import { canonicalize, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const realPath = await canonicalize('current', { baseDir: BaseDirectory.AppData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function canonicalize(
+  path: string | URL,
+  options?: CanonicalizeOptions
+): Promise<string> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|canonicalize', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * Size of a directory tree, see {@linkcode dirSize}.
+ *
+ * @since 2.1.0
+ */
+interface DirSize {
+  /** Sum of the sizes of all files, in bytes. */
+  bytes: number
+  /** Number of files, symlinks included. */
+  files: number
+  /** Number of sub directories. */
+  directories: number
+}
+
+/**
+ * @since 2.1.0
+ */
+interface DirSizeOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+  /**
+   * Count the size of files with several hard links only once.
+   * Only supported on Unix.
+   */
+  countHardLinksOnce?: boolean
+  /** Called periodically with the totals so far. */
+  onProgress?: (progress: DirSize) => void
+}
+
+/**
+ * Recursively sums the size of the files under `path` without following symlinks.
+ * Entries outside of the scope or that cannot be read are skipped.
+ * @example
+ * ```typescript
+ * import { dirSize, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const { bytes } = await dirSize('cache', { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function dirSize(
+  path: string | URL,
+  options?: DirSizeOptions
+): Promise<DirSize> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const { onProgress, ...opts } = options ?? {}
+  const channel = new Channel<DirSize>()
+  if (onProgress) {
+    channel.onmessage = onProgress
+  }
+
+  return await invoke('plugin:fs|dir_size', {
+    path: path instanceof URL ? path.toString() : path,
+    options: opts,
+    onProgress: channel
+  })
+}
+
+/**
+ * Size and free space of a filesystem, see {@linkcode statfs}.
+ *
+ * @since 2.1.0
+ */
+interface FsStats {
+  /** Size of the filesystem in bytes. */
+  total: number
+  /** Free bytes, including the ones reserved for privileged users. */
+  free: number
+  /** Free bytes available to the current user. */
+  available: number
+  /** Type of the filesystem, e.g. `ext4`, `apfs` or `NTFS`, if it could be determined. */
+  fsType: string | null
+}
+
+/**
+ * @since 2.1.0
+ */
+interface StatfsOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Resolves to the size and free space of the filesystem holding `path`.
+ * @example
+ * ```typescript
+ * import { statfs, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const { available } = await statfs('', { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function statfs(
+  path: string | URL,
+  options?: StatfsOptions
+): Promise<FsStats> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|statfs', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * @since 2.1.0
+ */
+interface MetadataOptions {
+  /** Base directory for `path` */
+  baseDir?: BaseDirectory
+  /**
+   * Change the file a symlink points to instead of the symlink itself. Defaults to `false`.
+   * The target of the symlink must also be allowed by the scope.
+   */
+  followSymlinks?: boolean
+}
+
+/**
+ * Changes the permissions of a file or directory. On Windows only the owner write bit is used,
+ * to toggle the read-only attribute.
+ *
+ * Only the permission bits `0o777` can be set, a mode with the setuid, setgid or sticky bit is rejected.
+ *
+ * Symlinks can only be changed with the `followSymlinks` option.
+ * @example
+ * ```typescript
+ * import { chmod, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await chmod('script.sh', 0o755, { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function chmod(
+  path: string | URL,
+  mode: number,
+  options?: MetadataOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|chmod', {
+    path: path instanceof URL ? path.toString() : path,
+    mode,
+    options
+  })
+}
+
+/**
+ * Changes the owner and group of a file or directory, `null` keeps the current value.
+ * Not supported on Windows.
+ * @example
+ * ```typescript
+ * import { chown, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await chown('file.txt', 1000, null, { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function chown(
+  path: string | URL,
+  uid: number | null,
+  gid: number | null,
+  options?: MetadataOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|chown', {
+    path: path instanceof URL ? path.toString() : path,
+    uid,
+    gid,
+    options
+  })
+}
+
+/**
+ * Sets the access and modification times of a file or directory, `null` keeps the current value.
+ * @example
+ * ```typescript
+ * import { utimes, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await utimes('file.txt', null, new Date(), { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function utimes(
+  path: string | URL,
+  atime: Date | number | null,
+  mtime: Date | number | null,
+  options?: MetadataOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|utimes', {
+    path: path instanceof URL ? path.toString() : path,
+    atime: atime instanceof Date ? atime.getTime() : atime,
+    mtime: mtime instanceof Date ? mtime.getTime() : mtime,
+    options
+  })
+}
+
+/**
+ * Reads an extended attribute, resolving to `null` if it is not set.
+ * Not supported on Windows.
+ * @example
+ * ```typescript
+ * import { getXattr, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const tag = await getXattr('file.txt', 'user.app.tag', { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function getXattr(
+  path: string | URL,
+  name: string,
+  options?: MetadataOptions
+): Promise<Uint8Array | null> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const value = await invoke<number[] | null>('plugin:fs|get_xattr', {
+    path: path instanceof URL ? path.toString() : path,
+    name,
+    options
+  })
+
+  return value !== null ? Uint8Array.from(value) : null
+}
+
+/**
+ * Sets an extended attribute. On Linux the name needs a namespace prefix, usually `user.`.
+ * Not supported on Windows.
+ * @example
+ * ```typescript
+ * import { setXattr, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await setXattr('file.txt', 'user.app.tag', new TextEncoder().encode('draft'), { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function setXattr(
+  path: string | URL,
+  name: string,
+  value: Uint8Array,
+  options?: MetadataOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|set_xattr', {
+    path: path instanceof URL ? path.toString() : path,
+    name,
+    value: Array.from(value),
+    options
+  })
+}
+
+/**
+ * Lists the names of the extended attributes of a file or directory.
+ * Not supported on Windows.
+ * @example
+ * ```typescript
+ * import { listXattr, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const names = await listXattr('file.txt', { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function listXattr(
+  path: string | URL,
+  options?: MetadataOptions
+): Promise<string[]> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  return await invoke('plugin:fs|list_xattr', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+}
+
+/**
+ * Removes an extended attribute.
+ * Not supported on Windows.
+ * @example
+ * ```typescript
+ * import { removeXattr, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await removeXattr('file.txt', 'user.app.tag', { baseDir: BaseDirectory.AppLocalData });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function removeXattr(
+  path: string | URL,
+  name: string,
+  options?: MetadataOptions
+): Promise<void> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  await invoke('plugin:fs|remove_xattr', {
+    path: path instanceof URL ? path.toString() : path,
+    name,
+    options
+  })
+}
+
+/**
+ * @since 2.0.0
+ */
+interface TruncateOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+}
+
+/**
+ * Truncates or extends the specified file, to reach the specified `len`.
+ * If `len` is `0` or not specified, then the entire file contents are truncated.
+ *
+ * @example
+ * ```typescript
+ * import { truncate, readTextFile, writeTextFile, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * // truncate the entire file
+ * await truncate("my_file.txt", 0, { baseDir: BaseDirectory.AppLocalData });
  *
  * // truncate part of the file
  * const filePath = "file.txt";
@@ -1016,6 +2031,21 @@ interface WriteFileOptions {
   mode?: number
   /** Base directory for `path` */
   baseDir?: BaseDirectory
+  /**
+   * Write to a temporary file first, sync it to disk and then rename it to `path`,
+   * so readers never see partially written content and a crash does not corrupt the file.
+   * The permissions of an existing file are kept. Cannot be combined with `append`.
+   * A symlink at `path` is replaced with a regular file instead of writing to its target.
+   *
+   * @since 2.1.0
+   */
+  atomic?: boolean
+  /**
+   * Sync the file content to disk before returning. Always done for `atomic` writes.
+   *
+   * @since 2.1.0
+   */
+  fsync?: boolean
 }
 
 /**
@@ -1078,6 +2108,69 @@ async function writeTextFile(
   })
 }
 
+/**
+ * A file written in chunks, created with {@linkcode createWriteStream}.
+ *
+ * Closing it without calling {@linkcode WriteStream.finish} discards an `atomic` write.
+ *
+ * @since 2.1.0
+ */
+class WriteStream extends Resource {
+  /**
+   * Appends `data` to the file.
+   *
+   * @since 2.1.0
+   */
+  async write(data: Uint8Array): Promise<void> {
+    await invoke('plugin:fs|write_stream', data, {
+      headers: { rid: this.rid.toString() }
+    })
+  }
+
+  /**
+   * Flushes the file, renames it to its destination for `atomic` writes and closes the stream.
+   *
+   * @since 2.1.0
+   */
+  async finish(): Promise<void> {
+    await invoke('plugin:fs|finish_write_stream', { rid: this.rid })
+  }
+}
+
+/**
+ * Opens a file to write it in chunks, by default creating a new file if needed, else overwriting.
+ *
+ * With the `atomic` option the chunks are written to a temporary file
+ * that only replaces `path` when {@linkcode WriteStream.finish} is called.
+ *
+ * @example
+ * ```typescript
+ * import { createWriteStream, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const stream = await createWriteStream('video.mp4', { baseDir: BaseDirectory.Video, atomic: true });
+ * for (const chunk of chunks) {
+ *   await stream.write(chunk);
+ * }
+ * await stream.finish();
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function createWriteStream(
+  path: string | URL,
+  options?: WriteFileOptions
+): Promise<WriteStream> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const rid = await invoke<number>('plugin:fs|create_write_stream', {
+    path: path instanceof URL ? path.toString() : path,
+    options
+  })
+
+  return new WriteStream(rid)
+}
+
 /**
  * @since 2.0.0
  */
@@ -1111,6 +2204,241 @@ async function exists(
   })
 }
 
+/**
+ * @since 2.1.0
+ */
+type HashAlgorithm = 'sha256' | 'sha1' | 'blake3' | 'crc32'
+
+/**
+ * Progress of a {@linkcode hash} or {@linkcode verify} operation.
+ *
+ * @since 2.1.0
+ */
+interface HashProgress {
+  /** Number of bytes hashed so far. */
+  bytesDone: number
+  /** Size of the file. */
+  bytesTotal: number
+}
+
+/**
+ * @since 2.1.0
+ */
+interface HashOptions {
+  /** Base directory for `path`. */
+  baseDir?: BaseDirectory
+  /** The hash algorithm, defaults to `'sha256'`. */
+  algorithm?: HashAlgorithm
+  /** Called periodically while the file is hashed. */
+  onProgress?: (progress: HashProgress) => void
+}
+
+function hashProgressChannel(
+  onProgress?: (progress: HashProgress) => void
+): Channel<HashProgress> {
+  const channel = new Channel<HashProgress>()
+  if (onProgress) {
+    channel.onmessage = onProgress
+  }
+  return channel
+}
+
+/**
+ * Computes the digest of a file without loading it into memory.
+ * Resolves to the digest as a lowercase hex string.
+ * @example
+ * ```typescript
+ * import { hash, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const digest = await hash('installer.exe', { baseDir: BaseDirectory.Download, algorithm: 'sha256' });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function hash(path: string | URL, options?: HashOptions): Promise<string> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const { onProgress, ...opts } = options ?? {}
+  return await invoke('plugin:fs|hash', {
+    path: path instanceof URL ? path.toString() : path,
+    options: opts,
+    onProgress: hashProgressChannel(onProgress)
+  })
+}
+
+/**
+ * Checks whether the digest of a file matches the `expected` hex digest, ignoring case.
+ * @example
+ * ```typescript
+ * import { verify, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * const valid = await verify('installer.exe', expectedSha256, { baseDir: BaseDirectory.Download });
+ * ```
+ *
+ * @since 2.1.0
+ */
+async function verify(
+  path: string | URL,
+  expected: string,
+  options?: HashOptions
+): Promise<boolean> {
+  if (path instanceof URL && path.protocol !== 'file:') {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const { onProgress, ...opts } = options ?? {}
+  return await invoke('plugin:fs|verify', {
+    path: path instanceof URL ? path.toString() : path,
+    expected,
+    options: opts,
+    onProgress: hashProgressChannel(onProgress)
+  })
+}
+
+/**
+ * Archive format, inferred from the archive extension (`.zip`, `.tar.gz` or `.tgz`) if not set.
+ *
+ * @since 2.1.0
+ */
+type ArchiveFormat = 'zip' | 'tarGz'
+
+/**
+ * Progress of a {@linkcode compress} or {@linkcode extract} operation.
+ *
+ * @since 2.1.0
+ */
+interface ArchiveProgress {
+  /** Number of entries processed so far. */
+  entriesDone: number
+  /** Total number of entries, `null` if it is not known upfront. */
+  entriesTotal: number | null
+  /** Number of uncompressed bytes processed so far. */
+  bytesDone: number
+  /** The path that was processed last. */
+  currentPath: string
+}
+
+/**
+ * @since 2.1.0
+ */
+interface CompressOptions {
+  /** Base directory for `sourcePath`. */
+  sourcePathBaseDir?: BaseDirectory
+  /** Base directory for `archivePath`. */
+  archivePathBaseDir?: BaseDirectory
+  /** Format of the archive. */
+  format?: ArchiveFormat
+  /** Called after each entry is added to the archive. */
+  onProgress?: (progress: ArchiveProgress) => void
+}
+
+/**
+ * @since 2.1.0
+ */
+interface ExtractOptions {
+  /** Base directory for `archivePath`. */
+  archivePathBaseDir?: BaseDirectory
+  /** Base directory for `destPath`. */
+  destPathBaseDir?: BaseDirectory
+  /** Format of the archive. */
+  format?: ArchiveFormat
+  /** Maximum number of entries in the archive. Defaults to `100000`. */
+  maxEntries?: number
+  /** Maximum number of bytes to extract. Defaults to 4 GiB. */
+  maxSize?: number
+  /** Called after each entry is extracted. */
+  onProgress?: (progress: ArchiveProgress) => void
+}
+
+function archiveProgressChannel(
+  onProgress?: (progress: ArchiveProgress) => void
+): Channel<ArchiveProgress> {
+  const channel = new Channel<ArchiveProgress>()
+  if (onProgress) {
+    channel.onmessage = onProgress
+  }
+  return channel
+}
+
+/**
+ * Compresses a file or the content of a directory into a zip or tar.gz archive.
+ * Symlinks are stored as links.
+ * @example
+ * ```typescript
+ * import { compress, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await compress('project', 'project.zip', {
+ *   sourcePathBaseDir: BaseDirectory.AppData,
+ *   archivePathBaseDir: BaseDirectory.Download
+ * });
+ * ```
+ *
+ * Requires the `archive` feature of the plugin.
+ *
+ * @since 2.1.0
+ */
+async function compress(
+  sourcePath: string | URL,
+  archivePath: string | URL,
+  options?: CompressOptions
+): Promise<void> {
+  if (
+    (sourcePath instanceof URL && sourcePath.protocol !== 'file:') ||
+    (archivePath instanceof URL && archivePath.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const { onProgress, ...opts } = options ?? {}
+  await invoke('plugin:fs|compress', {
+    sourcePath: sourcePath instanceof URL ? sourcePath.toString() : sourcePath,
+    archivePath:
+      archivePath instanceof URL ? archivePath.toString() : archivePath,
+    options: opts,
+    onProgress: archiveProgressChannel(onProgress)
+  })
+}
+
+/**
+ * Extracts a zip or tar.gz archive into a directory.
+ *
+ * Entries escaping the destination directory or outside of the fs scope are rejected,
+ * symlinks and special files are skipped.
+ * @example
+ * ```typescript
+ * import { extract, BaseDirectory } from '@tauri-apps/plugin-fs';
+ * await extract('project.zip', 'project', {
+ *   archivePathBaseDir: BaseDirectory.Download,
+ *   destPathBaseDir: BaseDirectory.AppData,
+ *   maxSize: 512 * 1024 * 1024
+ * });
+ * ```
+ *
+ * Requires the `archive` feature of the plugin.
+ *
+ * @since 2.1.0
+ */
+async function extract(
+  archivePath: string | URL,
+  destPath: string | URL,
+  options?: ExtractOptions
+): Promise<void> {
+  if (
+    (archivePath instanceof URL && archivePath.protocol !== 'file:') ||
+    (destPath instanceof URL && destPath.protocol !== 'file:')
+  ) {
+    throw new TypeError('Must be a file URL.')
+  }
+
+  const { onProgress, ...opts } = options ?? {}
+  await invoke('plugin:fs|extract', {
+    archivePath:
+      archivePath instanceof URL ? archivePath.toString() : archivePath,
+    destPath: destPath instanceof URL ? destPath.toString() : destPath,
+    options: opts,
+    onProgress: archiveProgressChannel(onProgress)
+  })
+}
+
 /**
  * @since 2.0.0
  */
@@ -1119,6 +2447,37 @@ interface WatchOptions {
   recursive?: boolean
   /** Base directory for `path` */
   baseDir?: BaseDirectory
+  /**
+   * Only report paths matching one of these glob patterns, relative to the watched path.
+   *
+   * @since 2.1.0
+   */
+  include?: string[]
+  /**
+   * Ignore paths matching one of these glob patterns, relative to the watched path, e.g. `node_modules/**`.
+   *
+   * @since 2.1.0
+   */
+  exclude?: string[]
+  /**
+   * Only report events of these kinds.
+   *
+   * @since 2.1.0
+   */
+  kinds?: Array<'access' | 'create' | 'modify' | 'remove' | 'other'>
+  /**
+   * Called when the watcher reports an error. The watcher keeps running afterwards.
+   *
+   * @since 2.1.0
+   */
+  onError?: (error: WatchError) => void
+  /**
+   * Called when events may have been missed, for instance because the OS event queue overflowed.
+   * Assume any file under the given paths might have changed and rescan them.
+   *
+   * @since 2.1.0
+   */
+  onRescan?: (paths: string[]) => void
 }
 
 /**
@@ -1138,6 +2497,22 @@ interface WatchEvent {
   attrs: unknown
 }
 
+/**
+ * An error reported by a watcher.
+ *
+ * @since 2.1.0
+ */
+interface WatchError {
+  message: string
+  /** The paths affected by the error, if known. */
+  paths: string[]
+}
+
+type WatcherMessage =
+  | { kind: 'event'; event: WatchEvent }
+  | ({ kind: 'error' } & WatchError)
+  | { kind: 'rescan'; paths: string[] }
+
 /**
  * @since 2.0.0
  */
@@ -1205,9 +2580,32 @@ async function unwatch(rid: number): Promise<void> {
   await invoke('plugin:fs|unwatch', { rid })
 }
 
+function watcherChannel(
+  cb: (event: WatchEvent) => void,
+  options?: WatchOptions
+): Channel<WatcherMessage> {
+  const onEvent = new Channel<WatcherMessage>()
+  onEvent.onmessage = (message) => {
+    switch (message.kind) {
+      case 'event':
+        cb(message.event)
+        break
+      case 'error':
+        options?.onError?.({ message: message.message, paths: message.paths })
+        break
+      case 'rescan':
+        options?.onRescan?.(message.paths)
+        break
+    }
+  }
+  return onEvent
+}
+
 /**
  * Watch changes (after a delay) on files or directories.
  *
+ * Events for paths outside of the scope are not reported.
+ *
  * @since 2.0.0
  */
 async function watch(
@@ -1215,10 +2613,12 @@ async function watch(
   cb: (event: WatchEvent) => void,
   options?: DebouncedWatchOptions
 ): Promise<UnwatchFn> {
+  const { onError, onRescan, ...rest } = options ?? {}
   const opts = {
     recursive: false,
     delayMs: 2000,
-    ...options
+    ...rest,
+    reportErrors: true
   }
 
   const watchPaths = Array.isArray(paths) ? paths : [paths]
@@ -1229,8 +2629,7 @@ async function watch(
     }
   }
 
-  const onEvent = new Channel<WatchEvent>()
-  onEvent.onmessage = cb
+  const onEvent = watcherChannel(cb, { onError, onRescan })
 
   const rid: number = await invoke('plugin:fs|watch', {
     paths: watchPaths.map((p) => (p instanceof URL ? p.toString() : p)),
@@ -1246,6 +2645,8 @@ async function watch(
 /**
  * Watch changes on files or directories.
  *
+ * Events for paths outside of the scope are not reported.
+ *
  * @since 2.0.0
  */
 async function watchImmediate(
@@ -1253,10 +2654,12 @@ async function watchImmediate(
   cb: (event: WatchEvent) => void,
   options?: WatchOptions
 ): Promise<UnwatchFn> {
+  const { onError, onRescan, ...rest } = options ?? {}
   const opts = {
     recursive: false,
-    ...options,
-    delayMs: null
+    ...rest,
+    delayMs: null,
+    reportErrors: true
   }
 
   const watchPaths = Array.isArray(paths) ? paths : [paths]
@@ -1267,8 +2670,7 @@ async function watchImmediate(
     }
   }
 
-  const onEvent = new Channel<WatchEvent>()
-  onEvent.onmessage = cb
+  const onEvent = watcherChannel(cb, { onError, onRescan })
 
   const rid: number = await invoke('plugin:fs|watch', {
     paths: watchPaths.map((p) => (p instanceof URL ? p.toString() : p)),
@@ -1284,21 +2686,48 @@ async function watchImmediate(
 export type {
   CreateOptions,
   OpenOptions,
+  TempOptions,
   CopyFileOptions,
+  CopyOptions,
+  CopyProgress,
   MkdirOptions,
   DirEntry,
   ReadDirOptions,
+  WalkDirOptions,
+  WalkEntry,
   ReadFileOptions,
+  ReadStreamOptions,
+  ReadFileStreamOptions,
+  LockMode,
   RemoveOptions,
+  TrashOptions,
+  TrashItem,
   RenameOptions,
   StatOptions,
+  SymlinkOptions,
+  HardLinkOptions,
+  ReadLinkOptions,
+  CanonicalizeOptions,
+  DirSize,
+  DirSizeOptions,
+  FsStats,
+  StatfsOptions,
+  MetadataOptions,
   TruncateOptions,
   WriteFileOptions,
   ExistsOptions,
   FileInfo,
+  HashAlgorithm,
+  HashOptions,
+  HashProgress,
+  ArchiveFormat,
+  ArchiveProgress,
+  CompressOptions,
+  ExtractOptions,
   WatchOptions,
   DebouncedWatchOptions,
   WatchEvent,
+  WatchError,
   WatchEventKind,
   WatchEventKindAccess,
   WatchEventKindCreate,
@@ -1310,23 +2739,51 @@ export type {
 export {
   BaseDirectory,
   FileHandle,
+  WriteStream,
+  TempPath,
   create,
   open,
+  createTempFile,
+  createTempDir,
   copyFile,
+  copy,
   mkdir,
   readDir,
+  walkDir,
   readFile,
+  readStream,
   readTextFile,
   readTextFileLines,
   remove,
+  trash,
+  listTrash,
+  restoreFromTrash,
   rename,
   SeekMode,
   stat,
   lstat,
+  symlink,
+  hardLink,
+  readLink,
+  canonicalize,
+  dirSize,
+  statfs,
+  chmod,
+  chown,
+  utimes,
+  getXattr,
+  setXattr,
+  listXattr,
+  removeXattr,
   truncate,
   writeFile,
   writeTextFile,
+  createWriteStream,
   exists,
+  hash,
+  verify,
+  compress,
+  extract,
   watch,
   watchImmediate
 }
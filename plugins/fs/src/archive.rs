@@ -0,0 +1,753 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    path::BaseDirectory,
+    scope::fs::Scope,
+    Runtime, Webview,
+};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use std::{
+    fs::{self, File},
+    io::{self, Read},
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    commands::{resolve_path, resolve_scope, CommandResult},
+    scope::Entry,
+    Error, SafeFilePath,
+};
+
+const DEFAULT_MAX_ENTRIES: u64 = 100_000;
+const DEFAULT_MAX_SIZE: u64 = 4 * 1024 * 1024 * 1024;
+
+const S_IFMT: u32 = 0o170000;
+const S_IFLNK: u32 = 0o120000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ArchiveFormat {
+    Zip,
+    TarGz,
+}
+
+impl ArchiveFormat {
+    fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        if name.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if name.ends_with(".tar.gz") || name.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else {
+            None
+        }
+    }
+
+    fn resolve(format: Option<Self>, archive_path: &Path) -> crate::Result<Self> {
+        format
+            .or_else(|| Self::from_path(archive_path))
+            .ok_or_else(|| {
+                Error::Io(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "unknown archive format, set the `format` option",
+                ))
+            })
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveProgress {
+    entries_done: u64,
+    /// `None` if the number of entries is not known upfront.
+    entries_total: Option<u64>,
+    bytes_done: u64,
+    current_path: PathBuf,
+}
+
+impl ArchiveProgress {
+    fn new(entries_total: Option<u64>) -> Self {
+        Self {
+            entries_done: 0,
+            entries_total,
+            bytes_done: 0,
+            current_path: PathBuf::new(),
+        }
+    }
+
+    fn entry_done(&mut self, path: &Path, bytes: u64, channel: &Channel<ArchiveProgress>) {
+        self.entries_done += 1;
+        self.bytes_done += bytes;
+        self.current_path = path.to_path_buf();
+        let _ = channel.send(self.clone());
+    }
+}
+
+fn invalid_data(message: String) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, message))
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CompressOptions {
+    source_path_base_dir: Option<BaseDirectory>,
+    archive_path_base_dir: Option<BaseDirectory>,
+    format: Option<ArchiveFormat>,
+}
+
+enum SourceKind {
+    Dir,
+    File(u64),
+    Symlink(PathBuf),
+}
+
+struct SourceEntry {
+    path: PathBuf,
+    /// Name inside the archive, always using `/` separators.
+    name: String,
+    kind: SourceKind,
+    mode: Option<u32>,
+}
+
+fn archive_name(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(unix)]
+fn unix_mode(metadata: &fs::Metadata) -> Option<u32> {
+    use std::os::unix::fs::PermissionsExt;
+    Some(metadata.permissions().mode() & 0o777)
+}
+
+#[cfg(not(unix))]
+fn unix_mode(_metadata: &fs::Metadata) -> Option<u32> {
+    None
+}
+
+/// Collects the entries to compress, checking every path against the scope.
+/// Symlinks are stored as links and never followed.
+fn collect_sources(
+    scope: &Scope,
+    root: &Path,
+    path: &Path,
+    archive_path: &Path,
+    entries: &mut Vec<SourceEntry>,
+) -> crate::Result<()> {
+    if !scope.is_allowed(path) {
+        return Err(Error::PathForbidden(path.to_path_buf()));
+    }
+    // do not add the archive to itself
+    if path == archive_path {
+        return Ok(());
+    }
+
+    let metadata = fs::symlink_metadata(path)?;
+    let relative = if path == root {
+        Path::new(root.file_name().unwrap_or_default())
+    } else {
+        path.strip_prefix(root).unwrap_or(path)
+    };
+    let name = archive_name(relative);
+    let mode = unix_mode(&metadata);
+
+    if metadata.file_type().is_symlink() {
+        entries.push(SourceEntry {
+            path: path.to_path_buf(),
+            name,
+            kind: SourceKind::Symlink(fs::read_link(path)?),
+            mode,
+        });
+    } else if metadata.is_dir() {
+        if path != root {
+            entries.push(SourceEntry {
+                path: path.to_path_buf(),
+                name,
+                kind: SourceKind::Dir,
+                mode,
+            });
+        }
+        let mut children = fs::read_dir(path)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<io::Result<Vec<_>>>()?;
+        children.sort();
+        for child in children {
+            collect_sources(scope, root, &child, archive_path, entries)?;
+        }
+    } else {
+        entries.push(SourceEntry {
+            path: path.to_path_buf(),
+            name,
+            kind: SourceKind::File(metadata.len()),
+            mode,
+        });
+    }
+
+    Ok(())
+}
+
+fn write_zip(
+    file: File,
+    entries: &[SourceEntry],
+    on_progress: &Channel<ArchiveProgress>,
+) -> crate::Result<File> {
+    let mut progress = ArchiveProgress::new(Some(entries.len() as u64));
+    let mut writer = ZipWriter::new(file);
+
+    for entry in entries {
+        let mut options =
+            SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+        if let Some(mode) = entry.mode {
+            options = options.unix_permissions(mode);
+        }
+
+        let bytes = match &entry.kind {
+            SourceKind::Dir => {
+                writer.add_directory(entry.name.as_str(), options)?;
+                0
+            }
+            SourceKind::Symlink(target) => {
+                writer.add_symlink(
+                    entry.name.as_str(),
+                    target.to_string_lossy().as_ref(),
+                    options,
+                )?;
+                0
+            }
+            SourceKind::File(_) => {
+                writer.start_file(entry.name.as_str(), options)?;
+                io::copy(&mut File::open(&entry.path)?, &mut writer)?
+            }
+        };
+        progress.entry_done(&entry.path, bytes, on_progress);
+    }
+
+    Ok(writer.finish()?)
+}
+
+fn write_tar_gz(
+    file: File,
+    entries: &[SourceEntry],
+    on_progress: &Channel<ArchiveProgress>,
+) -> crate::Result<File> {
+    let mut progress = ArchiveProgress::new(Some(entries.len() as u64));
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::default()));
+    builder.follow_symlinks(false);
+
+    for entry in entries {
+        builder.append_path_with_name(&entry.path, &entry.name)?;
+        let bytes = match entry.kind {
+            SourceKind::File(len) => len,
+            _ => 0,
+        };
+        progress.entry_done(&entry.path, bytes, on_progress);
+    }
+
+    Ok(builder.into_inner()?.finish()?)
+}
+
+#[tauri::command]
+pub async fn compress<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    source_path: SafeFilePath,
+    archive_path: SafeFilePath,
+    options: Option<CompressOptions>,
+    on_progress: Channel<ArchiveProgress>,
+) -> CommandResult<()> {
+    let source_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        source_path,
+        options.as_ref().and_then(|o| o.source_path_base_dir),
+    )?;
+    let archive_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        archive_path,
+        options.as_ref().and_then(|o| o.archive_path_base_dir),
+    )?;
+    let format = ArchiveFormat::resolve(options.and_then(|o| o.format), &archive_path)?;
+
+    let scope = resolve_scope(&webview, &global_scope, &command_scope)?;
+    let mut entries = Vec::new();
+    collect_sources(
+        &scope,
+        &source_path,
+        &source_path,
+        &archive_path,
+        &mut entries,
+    )?;
+
+    let write_archive = || -> crate::Result<()> {
+        let file = File::create(&archive_path)?;
+        let file = match format {
+            ArchiveFormat::Zip => write_zip(file, &entries, &on_progress)?,
+            ArchiveFormat::TarGz => write_tar_gz(file, &entries, &on_progress)?,
+        };
+        file.sync_all()?;
+        Ok(())
+    };
+
+    write_archive().map_err(|e| {
+        let _ = fs::remove_file(&archive_path);
+        format!(
+            "failed to compress path: {} to archive: {} with error: {e}",
+            source_path.display(),
+            archive_path.display()
+        )
+        .into()
+    })
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExtractOptions {
+    archive_path_base_dir: Option<BaseDirectory>,
+    dest_path_base_dir: Option<BaseDirectory>,
+    format: Option<ArchiveFormat>,
+    max_entries: Option<u64>,
+    max_size: Option<u64>,
+}
+
+/// Writes archive entries below a destination directory,
+/// enforcing the scope, path traversal protection and the configured limits.
+struct Extractor<'a> {
+    scope: Scope,
+    dest: &'a Path,
+    canonical_dest: PathBuf,
+    max_entries: u64,
+    max_size: u64,
+    entries: u64,
+    bytes: u64,
+    progress: ArchiveProgress,
+    on_progress: &'a Channel<ArchiveProgress>,
+}
+
+impl Extractor<'_> {
+    /// Resolves the destination of an archive entry.
+    ///
+    /// Returns `None` for entries that resolve to the destination directory itself.
+    fn target(&mut self, name: &Path) -> crate::Result<Option<PathBuf>> {
+        self.entries += 1;
+        if self.entries > self.max_entries {
+            return Err(invalid_data(format!(
+                "archive has more than {} entries",
+                self.max_entries
+            )));
+        }
+
+        let mut relative = PathBuf::new();
+        for component in name.components() {
+            match component {
+                Component::Normal(part) => relative.push(part),
+                Component::CurDir => (),
+                _ => {
+                    return Err(invalid_data(format!(
+                        "archive entry {} escapes the destination directory",
+                        name.display()
+                    )))
+                }
+            }
+        }
+        if relative.as_os_str().is_empty() {
+            return Ok(None);
+        }
+
+        let target = self.dest.join(relative);
+        if !self.scope.is_allowed(&target) {
+            return Err(Error::PathForbidden(target));
+        }
+        Ok(Some(target))
+    }
+
+    /// Creates `target` one component at a time, so no directory is ever created through an existing symlink.
+    fn create_dir(&self, target: &Path) -> crate::Result<()> {
+        let relative = target.strip_prefix(self.dest).map_err(|_| {
+            invalid_data(format!(
+                "{} is outside of the destination directory",
+                target.display()
+            ))
+        })?;
+        let mut dir = self.dest.to_path_buf();
+        for component in relative.components() {
+            dir.push(component);
+            match fs::symlink_metadata(&dir) {
+                Ok(metadata) if metadata.file_type().is_symlink() => {
+                    return Err(invalid_data(format!(
+                        "{} is an existing symlink and cannot be extracted into",
+                        dir.display()
+                    )))
+                }
+                Ok(metadata) if metadata.is_dir() => (),
+                _ => fs::create_dir(&dir)?,
+            }
+        }
+        self.check_inside_dest(target)
+    }
+
+    /// Makes sure `path` did not end up outside of the destination through an existing symlink.
+    fn check_inside_dest(&self, path: &Path) -> crate::Result<()> {
+        if dunce::canonicalize(path)?.starts_with(&self.canonical_dest) {
+            Ok(())
+        } else {
+            Err(invalid_data(format!(
+                "{} resolves outside of the destination directory",
+                path.display()
+            )))
+        }
+    }
+
+    fn write_file(
+        &mut self,
+        target: &Path,
+        mut reader: impl Read,
+        mode: Option<u32>,
+    ) -> crate::Result<()> {
+        if let Some(parent) = target.parent() {
+            self.create_dir(parent)?;
+        }
+        // never write through an existing symlink
+        if fs::symlink_metadata(target).is_ok_and(|m| m.file_type().is_symlink()) {
+            fs::remove_file(target)?;
+        }
+
+        let mut file = File::create(target)?;
+        // the sizes stored in the archive cannot be trusted, count what is actually written
+        let remaining = self.max_size - self.bytes;
+        let written = io::copy(
+            &mut (&mut reader).take(remaining.saturating_add(1)),
+            &mut file,
+        )?;
+        if written > remaining {
+            drop(file);
+            let _ = fs::remove_file(target);
+            return Err(invalid_data(format!(
+                "archive content is larger than {} bytes",
+                self.max_size
+            )));
+        }
+        self.bytes += written;
+
+        #[cfg(unix)]
+        if let Some(mode) = mode {
+            use std::os::unix::fs::PermissionsExt;
+            file.set_permissions(fs::Permissions::from_mode(mode & 0o777))?;
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+
+        self.progress.entry_done(target, written, self.on_progress);
+        Ok(())
+    }
+
+    fn skip(&mut self, target: &Path) {
+        self.progress.entry_done(target, 0, self.on_progress);
+    }
+
+    fn extract_zip(&mut self, file: File) -> crate::Result<()> {
+        let mut archive = ZipArchive::new(file)?;
+        self.progress.entries_total = Some(archive.len() as u64);
+
+        for i in 0..archive.len() {
+            let entry = archive.by_index(i)?;
+            // `enclosed_name` rejects absolute paths and `..` traversal
+            let name = entry.enclosed_name().ok_or_else(|| {
+                invalid_data(format!("archive entry {} has an unsafe path", entry.name()))
+            })?;
+            let Some(target) = self.target(&name)? else {
+                continue;
+            };
+
+            let mode = entry.unix_mode();
+            if entry.is_dir() {
+                self.create_dir(&target)?;
+                self.skip(&target);
+            } else if mode.is_some_and(|m| m & S_IFMT == S_IFLNK) {
+                // symlinks could point outside of the destination directory
+                self.skip(&target);
+            } else {
+                self.write_file(&target, entry, mode)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn extract_tar_gz(&mut self, file: File) -> crate::Result<()> {
+        let mut archive = tar::Archive::new(GzDecoder::new(file));
+
+        for entry in archive.entries()? {
+            let entry = entry?;
+            let name = entry.path()?.into_owned();
+            let Some(target) = self.target(&name)? else {
+                continue;
+            };
+
+            let entry_type = entry.header().entry_type();
+            if entry_type.is_dir() {
+                self.create_dir(&target)?;
+                self.skip(&target);
+            } else if entry_type.is_file() {
+                let mode = entry.header().mode().ok();
+                self.write_file(&target, entry, mode)?;
+            } else {
+                // symlinks, hard links and special files are not extracted
+                self.skip(&target);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[tauri::command]
+pub async fn extract<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    archive_path: SafeFilePath,
+    dest_path: SafeFilePath,
+    options: Option<ExtractOptions>,
+    on_progress: Channel<ArchiveProgress>,
+) -> CommandResult<()> {
+    let archive_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        archive_path,
+        options.as_ref().and_then(|o| o.archive_path_base_dir),
+    )?;
+    let dest_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        dest_path,
+        options.as_ref().and_then(|o| o.dest_path_base_dir),
+    )?;
+    let format = ArchiveFormat::resolve(options.as_ref().and_then(|o| o.format), &archive_path)?;
+
+    let scope = resolve_scope(&webview, &global_scope, &command_scope)?;
+
+    let extract_archive = || -> crate::Result<()> {
+        fs::create_dir_all(&dest_path)?;
+        let mut extractor = Extractor {
+            scope,
+            dest: &dest_path,
+            canonical_dest: dunce::canonicalize(&dest_path)?,
+            max_entries: options
+                .as_ref()
+                .and_then(|o| o.max_entries)
+                .unwrap_or(DEFAULT_MAX_ENTRIES),
+            max_size: options
+                .as_ref()
+                .and_then(|o| o.max_size)
+                .unwrap_or(DEFAULT_MAX_SIZE),
+            entries: 0,
+            bytes: 0,
+            progress: ArchiveProgress::new(None),
+            on_progress: &on_progress,
+        };
+
+        let file = File::open(&archive_path)?;
+        match format {
+            ArchiveFormat::Zip => extractor.extract_zip(file),
+            ArchiveFormat::TarGz => extractor.extract_tar_gz(file),
+        }
+    };
+
+    extract_archive()
+        .map_err(|e| {
+            format!(
+                "failed to extract archive: {} to path: {} with error: {e}",
+                archive_path.display(),
+                dest_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tauri::{
+        test::{mock_builder, mock_context, noop_assets},
+        utils::config::FsScope,
+    };
+
+    /// A new directory in the temporary directory, removed on drop.
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new() -> Self {
+            let path = std::env::temp_dir().join(uuid::Uuid::new_v4().simple().to_string());
+            fs::create_dir(&path).unwrap();
+            Self(dunce::canonicalize(path).unwrap())
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn extract(
+        dir: &Path,
+        archive: &Path,
+        format: ArchiveFormat,
+        max_size: u64,
+    ) -> crate::Result<()> {
+        let app = mock_builder().build(mock_context(noop_assets())).unwrap();
+        let scope = Scope::new(
+            &app,
+            &FsScope::Scope {
+                allow: vec![dir.join("**")],
+                deny: Vec::new(),
+                require_literal_leading_dot: None,
+            },
+        )
+        .unwrap();
+        let on_progress = Channel::new(|_| Ok(()));
+
+        let dest = dir.join("dest");
+        fs::create_dir_all(&dest)?;
+        let mut extractor = Extractor {
+            scope,
+            dest: &dest,
+            canonical_dest: dunce::canonicalize(&dest)?,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            max_size,
+            entries: 0,
+            bytes: 0,
+            progress: ArchiveProgress::new(None),
+            on_progress: &on_progress,
+        };
+        let file = File::open(archive)?;
+        match format {
+            ArchiveFormat::Zip => extractor.extract_zip(file),
+            ArchiveFormat::TarGz => extractor.extract_tar_gz(file),
+        }
+    }
+
+    fn zip(path: &Path, add: impl FnOnce(&mut ZipWriter<File>) -> zip::result::ZipResult<()>) {
+        let mut writer = ZipWriter::new(File::create(path).unwrap());
+        add(&mut writer).unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn zip_slip_is_rejected() {
+        let dir = TempDir::new();
+        let archive = dir.0.join("slip.zip");
+        zip(&archive, |writer| {
+            writer.start_file("../evil.txt", SimpleFileOptions::default())?;
+            io::Write::write_all(writer, b"evil")?;
+            Ok(())
+        });
+
+        assert!(extract(&dir.0, &archive, ArchiveFormat::Zip, DEFAULT_MAX_SIZE).is_err());
+        assert!(!dir.0.join("evil.txt").exists());
+    }
+
+    #[test]
+    fn tar_slip_is_rejected() {
+        let dir = TempDir::new();
+        let archive = dir.0.join("slip.tar.gz");
+        let mut builder = tar::Builder::new(GzEncoder::new(
+            File::create(&archive).unwrap(),
+            Compression::default(),
+        ));
+        let mut header = tar::Header::new_gnu();
+        // `set_path` refuses `..`, so the name is written directly
+        header.as_gnu_mut().unwrap().name[..11].copy_from_slice(b"../evil.txt");
+        header.set_size(4);
+        header.set_mode(0o644);
+        header.set_cksum();
+        builder.append(&header, &b"evil"[..]).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        assert!(extract(&dir.0, &archive, ArchiveFormat::TarGz, DEFAULT_MAX_SIZE).is_err());
+        assert!(!dir.0.join("evil.txt").exists());
+    }
+
+    #[test]
+    fn symlinks_are_not_extracted() {
+        let dir = TempDir::new();
+        let archive = dir.0.join("link.zip");
+        zip(&archive, |writer| {
+            writer.add_symlink(
+                "link",
+                dir.0.to_string_lossy(),
+                SimpleFileOptions::default(),
+            )?;
+            writer.start_file("link/evil.txt", SimpleFileOptions::default())?;
+            io::Write::write_all(writer, b"evil")?;
+            Ok(())
+        });
+
+        extract(&dir.0, &archive, ArchiveFormat::Zip, DEFAULT_MAX_SIZE).unwrap();
+        let link = dir.0.join("dest/link");
+        assert!(!fs::symlink_metadata(&link).unwrap().is_symlink());
+        assert!(link.join("evil.txt").is_file());
+        assert!(!dir.0.join("evil.txt").exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn existing_symlinks_are_not_followed() {
+        let dir = TempDir::new();
+        let outside = dir.0.join("outside");
+        fs::create_dir_all(&outside).unwrap();
+        fs::create_dir_all(dir.0.join("dest")).unwrap();
+        std::os::unix::fs::symlink(&outside, dir.0.join("dest/link")).unwrap();
+
+        let archive = dir.0.join("link.zip");
+        zip(&archive, |writer| {
+            writer.start_file("link/evil.txt", SimpleFileOptions::default())?;
+            io::Write::write_all(writer, b"evil")?;
+            Ok(())
+        });
+
+        assert!(extract(&dir.0, &archive, ArchiveFormat::Zip, DEFAULT_MAX_SIZE).is_err());
+
+        // the directories of a nested entry are not created through the symlink either
+        zip(&archive, |writer| {
+            writer.start_file("link/a/evil.txt", SimpleFileOptions::default())?;
+            io::Write::write_all(writer, b"evil")?;
+            Ok(())
+        });
+        assert!(extract(&dir.0, &archive, ArchiveFormat::Zip, DEFAULT_MAX_SIZE).is_err());
+
+        assert!(fs::read_dir(&outside).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn size_limit_is_enforced() {
+        let dir = TempDir::new();
+        let archive = dir.0.join("bomb.zip");
+        zip(&archive, |writer| {
+            writer.start_file("big.bin", SimpleFileOptions::default())?;
+            io::Write::write_all(writer, &[0; 1000])?;
+            Ok(())
+        });
+
+        assert!(extract(&dir.0, &archive, ArchiveFormat::Zip, 999).is_err());
+        assert!(!dir.0.join("dest/big.bin").exists());
+        extract(&dir.0, &archive, ArchiveFormat::Zip, 1000).unwrap();
+        assert_eq!(fs::read(dir.0.join("dest/big.bin")).unwrap().len(), 1000);
+    }
+}
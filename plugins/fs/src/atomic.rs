@@ -0,0 +1,209 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Returns a unique path next to `path` for a temporary file.
+fn temp_sibling(path: &Path) -> io::Result<PathBuf> {
+    let file_name = path.file_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("{} is not a file path", path.display()),
+        )
+    })?;
+    let mut temp_name = OsString::from(".");
+    temp_name.push(file_name);
+    temp_name.push(format!(".{}.tmp", uuid::Uuid::new_v4()));
+    Ok(path.with_file_name(temp_name))
+}
+
+/// A temporary file next to its destination that replaces the destination on [`AtomicFile::commit`].
+///
+/// The temporary file is removed if it is dropped without being committed.
+pub(crate) struct AtomicFile {
+    file: Option<File>,
+    temp_path: PathBuf,
+    path: PathBuf,
+    persisted: bool,
+}
+
+impl AtomicFile {
+    /// Creates the temporary file for `path`, using `mode` as its permissions on Unix.
+    #[cfg_attr(not(unix), allow(unused_variables))]
+    pub(crate) fn create(path: &Path, mode: Option<u32>) -> io::Result<Self> {
+        let temp_path = temp_sibling(path)?;
+
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        if let Some(mode) = mode {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(mode);
+        }
+
+        Ok(Self {
+            file: Some(options.open(&temp_path)?),
+            temp_path,
+            path: path.to_path_buf(),
+            persisted: false,
+        })
+    }
+
+    pub(crate) fn file(&self) -> &File {
+        self.file.as_ref().expect("atomic file already committed")
+    }
+
+    /// Syncs the temporary file to disk and renames it over the destination,
+    /// so readers either see the old or the new content and a crash never leaves a partial file.
+    ///
+    /// If the destination already exists its permissions are kept.
+    /// A symlink at the destination is replaced, not followed,
+    /// so the new file keeps the permissions it was created with instead of the ones of the link target.
+    pub(crate) fn commit(mut self) -> io::Result<()> {
+        let file = self.file.take().expect("atomic file already committed");
+        match fs::symlink_metadata(&self.path) {
+            Ok(existing) if !existing.file_type().is_symlink() => {
+                file.set_permissions(existing.permissions())?;
+            }
+            _ => (),
+        }
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&self.temp_path, &self.path)?;
+        self.persisted = true;
+        sync_parent(&self.path)
+    }
+}
+
+impl Drop for AtomicFile {
+    fn drop(&mut self) {
+        if !self.persisted {
+            let _ = fs::remove_file(&self.temp_path);
+        }
+    }
+}
+
+/// Writes `data` to a temporary file next to `path` and renames it over `path`,
+/// see [`AtomicFile::commit`].
+///
+/// If `path` does not exist yet `mode` is used as its permissions on Unix.
+pub(crate) fn write_atomic(path: &Path, data: &[u8], mode: Option<u32>) -> io::Result<()> {
+    let file = AtomicFile::create(path, mode)?;
+    file.file().write_all(data)?;
+    file.commit()
+}
+
+/// Persists the rename of a file by syncing its parent directory.
+#[cfg(unix)]
+fn sync_parent(path: &Path) -> io::Result<()> {
+    match path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        Some(parent) => File::open(parent)?.sync_all(),
+        None => File::open(".")?.sync_all(),
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent(_path: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().simple().to_string());
+        fs::create_dir(&dir).unwrap();
+        dir
+    }
+
+    fn temp_files(dir: &Path) -> usize {
+        fs::read_dir(dir)
+            .unwrap()
+            .filter(|entry| {
+                entry
+                    .as_ref()
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .ends_with(".tmp")
+            })
+            .count()
+    }
+
+    #[test]
+    fn commit_replaces_the_destination() {
+        let dir = temp_dir();
+        let path = dir.join("file.txt");
+        fs::write(&path, "old").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o600)).unwrap();
+        }
+
+        let file = AtomicFile::create(&path, None).unwrap();
+        file.file().write_all(b"new").unwrap();
+        // the destination is untouched until the commit
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        file.commit().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(temp_files(&dir), 0);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn commit_replaces_a_symlink() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir();
+        let target = dir.join("target.txt");
+        fs::write(&target, "target").unwrap();
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o755)).unwrap();
+        let path = dir.join("link.txt");
+        std::os::unix::fs::symlink(&target, &path).unwrap();
+
+        write_atomic(&path, b"new", Some(0o600)).unwrap();
+
+        let metadata = fs::symlink_metadata(&path).unwrap();
+        assert!(metadata.is_file());
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+        assert_eq!(fs::read_to_string(&target).unwrap(), "target");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn dropped_file_is_discarded() {
+        let dir = temp_dir();
+        let path = dir.join("file.txt");
+        fs::write(&path, "old").unwrap();
+
+        let file = AtomicFile::create(&path, None).unwrap();
+        file.file().write_all(b"new").unwrap();
+        assert_eq!(temp_files(&dir), 1);
+        drop(file);
+
+        assert_eq!(temp_files(&dir), 0);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -18,11 +18,21 @@ use std::{
     io::{BufReader, Lines, Read, Write},
     path::{Path, PathBuf},
     str::FromStr,
-    sync::Mutex,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
     time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::{scope::Entry, Error, FsExt, SafeFilePath};
+use tokio::sync::Semaphore;
+
+use crate::{
+    lock::{self, LockMode},
+    scope::Entry,
+    temp::TempPaths,
+    Error, FsExt, SafeFilePath,
+};
 
 #[derive(Debug, thiserror::Error)]
 pub enum CommandError {
@@ -73,7 +83,7 @@ pub type CommandResult<T> = std::result::Result<T, CommandError>;
 #[derive(Debug, Default, Clone, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct BaseOptions {
-    base_dir: Option<BaseDirectory>,
+    pub(crate) base_dir: Option<BaseDirectory>,
 }
 
 #[tauri::command]
@@ -105,9 +115,9 @@ pub fn create<R: Runtime>(
 #[serde(rename_all = "camelCase")]
 pub struct OpenOptions {
     #[serde(flatten)]
-    base: BaseOptions,
+    pub(crate) base: BaseOptions,
     #[serde(flatten)]
-    options: crate::OpenOptions,
+    pub(crate) options: crate::OpenOptions,
 }
 
 #[tauri::command]
@@ -787,15 +797,19 @@ pub async fn write<R: Runtime>(
 #[serde(rename_all = "camelCase")]
 pub struct WriteFileOptions {
     #[serde(flatten)]
-    base: BaseOptions,
+    pub(crate) base: BaseOptions,
     #[serde(default)]
-    append: bool,
+    pub(crate) append: bool,
     #[serde(default = "default_create_value")]
-    create: bool,
+    pub(crate) create: bool,
     #[serde(default)]
-    create_new: bool,
+    pub(crate) create_new: bool,
     #[allow(unused)]
-    mode: Option<u32>,
+    pub(crate) mode: Option<u32>,
+    #[serde(default)]
+    pub(crate) atomic: bool,
+    #[serde(default)]
+    pub(crate) fsync: bool,
 }
 
 fn default_create_value() -> bool {
@@ -834,6 +848,11 @@ fn write_file_inner<R: Runtime>(
         .and_then(|p| p.to_str().ok())
         .and_then(|opts| serde_json::from_str(opts).ok());
 
+    if let Some(opts) = options.as_ref().filter(|o| o.atomic) {
+        return write_file_atomic(&webview, global_scope, command_scope, path, opts, &data);
+    }
+    let fsync = options.as_ref().is_some_and(|o| o.fsync);
+
     let (mut file, path) = resolve_file(
         &webview,
         global_scope,
@@ -871,6 +890,7 @@ fn write_file_inner<R: Runtime>(
     )?;
 
     file.write_all(&data)
+        .and_then(|_| if fsync { file.sync_all() } else { Ok(()) })
         .map_err(|e| {
             format!(
                 "failed to write bytes to file at path: {} with error: {e}",
@@ -880,6 +900,57 @@ fn write_file_inner<R: Runtime>(
         .map_err(Into::into)
 }
 
+fn write_file_atomic<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+    path: SafeFilePath,
+    options: &WriteFileOptions,
+    data: &[u8],
+) -> CommandResult<()> {
+    let path = resolve_path(
+        webview,
+        global_scope,
+        command_scope,
+        path,
+        options.base.base_dir,
+    )?;
+
+    check_atomic_write(&path, options)?;
+
+    crate::atomic::write_atomic(&path, data, options.mode)
+        .map_err(|e| {
+            format!(
+                "failed to write bytes to file at path: {} with error: {e}",
+                path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+/// Checks that the options of an atomic write to `path` can be honored.
+pub(crate) fn check_atomic_write(path: &Path, options: &WriteFileOptions) -> CommandResult<()> {
+    if options.append {
+        return Err("the `append` option cannot be used with `atomic` writes".into());
+    }
+    let exists = std::fs::symlink_metadata(path).is_ok();
+    if exists && options.create_new {
+        return Err(format!(
+            "failed to create file at path: {}, it already exists",
+            path.display()
+        )
+        .into());
+    }
+    if !exists && !options.create {
+        return Err(format!(
+            "failed to write to path: {}, it does not exist",
+            path.display()
+        )
+        .into());
+    }
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn write_file<R: Runtime>(
     webview: Webview<R>,
@@ -996,7 +1067,22 @@ pub fn resolve_path<R: Runtime>(
         path
     };
 
-    let scope = tauri::scope::fs::Scope::new(
+    let scope = resolve_scope(webview, global_scope, command_scope)?;
+
+    if scope.is_allowed(&path) {
+        Ok(path)
+    } else {
+        Err(CommandError::Plugin(Error::PathForbidden(path)))
+    }
+}
+
+/// Builds the fs scope of a command invocation from the runtime, global and command scopes.
+pub fn resolve_scope<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+) -> CommandResult<tauri::scope::fs::Scope> {
+    tauri::scope::fs::Scope::new(
         webview,
         &FsScope::Scope {
             allow: webview
@@ -1006,6 +1092,7 @@ pub fn resolve_path<R: Runtime>(
                 .unwrap()
                 .clone()
                 .into_iter()
+                .chain(webview.state::<TempPaths>().allowed(webview.label()))
                 .chain(global_scope.allows().iter().filter_map(|e| e.path.clone()))
                 .chain(command_scope.allows().iter().filter_map(|e| e.path.clone()))
                 .collect(),
@@ -1021,29 +1108,99 @@ pub fn resolve_path<R: Runtime>(
                 .collect(),
             require_literal_leading_dot: webview.fs_scope().require_literal_leading_dot,
         },
-    )?;
+    )
+    .map_err(Into::into)
+}
 
-    if scope.is_allowed(&path) {
-        Ok(path)
-    } else {
-        Err(CommandError::Plugin(Error::PathForbidden(path)))
-    }
+/// Parses user provided glob patterns.
+pub(crate) fn parse_patterns(patterns: &[String]) -> crate::Result<Vec<glob::Pattern>> {
+    patterns
+        .iter()
+        .map(|p| glob::Pattern::new(p).map_err(Into::into))
+        .collect()
 }
 
-struct StdFileResource(Mutex<File>);
+pub(crate) struct StdFileResource {
+    file: Mutex<File>,
+    /// Credits of the running `read_stream`, one per chunk the consumer is ready to receive.
+    stream: Mutex<Option<Arc<Semaphore>>>,
+    /// Mode of the lock held through this handle.
+    lock_mode: Mutex<Option<LockMode>>,
+    closed: AtomicBool,
+}
 
 impl StdFileResource {
-    fn new(file: File) -> Self {
-        Self(Mutex::new(file))
+    pub(crate) fn new(file: File) -> Self {
+        Self {
+            file: Mutex::new(file),
+            stream: Mutex::new(None),
+            lock_mode: Mutex::new(None),
+            closed: AtomicBool::new(false),
+        }
     }
 
-    fn with_lock<R, F: FnMut(&File) -> R>(&self, mut f: F) -> R {
-        let file = self.0.lock().unwrap();
+    pub(crate) fn with_lock<R, F: FnMut(&File) -> R>(&self, mut f: F) -> R {
+        let file = self.file.lock().unwrap();
         f(&file)
     }
+
+    /// Starts a stream with `credits` initial credits, failing if one is already running.
+    pub(crate) fn start_stream(&self, credits: usize) -> Option<Arc<Semaphore>> {
+        let mut stream = self.stream.lock().unwrap();
+        if stream.is_some() {
+            return None;
+        }
+        let semaphore = Arc::new(Semaphore::new(credits));
+        *stream = Some(semaphore.clone());
+        Some(semaphore)
+    }
+
+    pub(crate) fn stream(&self) -> Option<Arc<Semaphore>> {
+        self.stream.lock().unwrap().clone()
+    }
+
+    pub(crate) fn end_stream(&self) {
+        if let Some(semaphore) = self.stream.lock().unwrap().take() {
+            semaphore.close();
+        }
+    }
+
+    /// Locks the file, releasing the lock already held through this handle first
+    /// since locks cannot be converted on all platforms.
+    ///
+    /// Returns `false` if the file is locked elsewhere and `wait` is not set.
+    pub(crate) fn lock(&self, mode: LockMode, wait: bool) -> std::io::Result<bool> {
+        self.unlock()?;
+        // the file mutex is not held while waiting so other operations are not blocked
+        let file = self.with_lock(lock::raw);
+        if !lock::acquire(file, mode, wait)? {
+            return Ok(false);
+        }
+        *self.lock_mode.lock().unwrap() = Some(mode);
+        // the resource was closed while waiting for the lock
+        if self.closed.load(Ordering::SeqCst) {
+            self.unlock()?;
+        }
+        Ok(true)
+    }
+
+    pub(crate) fn unlock(&self) -> std::io::Result<()> {
+        if self.lock_mode.lock().unwrap().take().is_some() {
+            lock::release(self.with_lock(lock::raw))?;
+        }
+        Ok(())
+    }
 }
 
-impl Resource for StdFileResource {}
+impl Resource for StdFileResource {
+    fn close(self: Arc<Self>) {
+        self.closed.store(true, Ordering::SeqCst);
+        // wakes up a stream waiting for credits
+        self.end_stream();
+        // the lock would otherwise be held until pending operations drop the file
+        let _ = self.unlock();
+    }
+}
 
 struct StdLinesResource(Mutex<Lines<BufReader<File>>>);
 
@@ -1104,7 +1261,7 @@ pub struct FileInfo {
 
 // taken from deno source code: https://github.com/denoland/deno/blob/ffffa2f7c44bd26aec5ae1957e0534487d099f48/runtime/ops/fs.rs#L950
 #[inline(always)]
-fn get_stat(metadata: std::fs::Metadata) -> FileInfo {
+pub(crate) fn get_stat(metadata: std::fs::Metadata) -> FileInfo {
     // Unix stat member (number types only). 0 if not on unix.
     macro_rules! usm {
         ($member:ident) => {{
@@ -0,0 +1,466 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use filetime::FileTime;
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    path::BaseDirectory,
+    scope::fs::Scope,
+    Runtime, Webview,
+};
+
+use std::{
+    fs::{self, File, Metadata},
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    commands::{resolve_path, resolve_scope, CommandResult},
+    link::real_path,
+    scope::Entry,
+    Error, SafeFilePath,
+};
+
+/// What to do when a destination path already exists.
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CopyConflict {
+    /// Replace the existing file.
+    #[default]
+    Overwrite,
+    /// Keep the existing file and do not copy the source.
+    Skip,
+    /// Abort the copy.
+    Error,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyOptions {
+    from_path_base_dir: Option<BaseDirectory>,
+    to_path_base_dir: Option<BaseDirectory>,
+    #[serde(default)]
+    on_conflict: CopyConflict,
+    #[serde(default)]
+    preserve_timestamps: bool,
+    #[serde(default = "default_true")]
+    preserve_permissions: bool,
+    #[serde(default)]
+    follow_symlinks: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyProgress {
+    files_done: u64,
+    files_total: u64,
+    bytes_done: u64,
+    bytes_total: u64,
+    current_path: PathBuf,
+}
+
+enum CopyKind {
+    Dir,
+    File,
+    Symlink,
+}
+
+struct CopyEntry {
+    from: PathBuf,
+    to: PathBuf,
+    kind: CopyKind,
+    metadata: Metadata,
+}
+
+/// Walks `from` and collects the entries to copy, checking every path against the scope.
+fn plan(
+    scope: &Scope,
+    from: &Path,
+    to: &Path,
+    options: &CopyOptions,
+    ancestors: &mut Vec<PathBuf>,
+    entries: &mut Vec<CopyEntry>,
+) -> crate::Result<()> {
+    for path in [from, to] {
+        if !scope.is_allowed(path) {
+            return Err(Error::PathForbidden(path.to_path_buf()));
+        }
+    }
+
+    let metadata = if options.follow_symlinks {
+        fs::metadata(from)?
+    } else {
+        fs::symlink_metadata(from)?
+    };
+
+    if metadata.is_dir() {
+        // following symlinks can lead back to a directory that is already being copied
+        let canonical = dunce::canonicalize(from)?;
+        if ancestors.contains(&canonical) {
+            return Err(Error::Io(io::Error::other(format!(
+                "symlink loop detected at {}",
+                from.display()
+            ))));
+        }
+
+        entries.push(CopyEntry {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+            kind: CopyKind::Dir,
+            metadata,
+        });
+
+        ancestors.push(canonical);
+        for child in fs::read_dir(from)? {
+            let child = child?;
+            plan(
+                scope,
+                &child.path(),
+                &to.join(child.file_name()),
+                options,
+                ancestors,
+                entries,
+            )?;
+        }
+        ancestors.pop();
+    } else {
+        let kind = if metadata.file_type().is_symlink() {
+            // the copied link resolves its target next to `to`, which must be allowed too
+            let target = fs::read_link(from)?;
+            let parent = to.parent().unwrap_or(Path::new(""));
+            let resolved_target = real_path(&parent.join(target));
+            if !scope.is_allowed(&resolved_target) {
+                return Err(Error::PathForbidden(resolved_target));
+            }
+            CopyKind::Symlink
+        } else {
+            CopyKind::File
+        };
+        entries.push(CopyEntry {
+            from: from.to_path_buf(),
+            to: to.to_path_buf(),
+            kind,
+            metadata,
+        });
+    }
+
+    Ok(())
+}
+
+/// Checks what exists at `to`.
+///
+/// Returns `true` if the entry should be copied, removing the existing file or symlink
+/// if it must be overwritten so nothing is ever written through a symlink.
+fn resolve_conflict(to: &Path, on_conflict: CopyConflict, is_dir: bool) -> io::Result<bool> {
+    let existing = match fs::symlink_metadata(to) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(true),
+        Err(e) => return Err(e),
+    };
+
+    // directories are merged
+    if is_dir && existing.is_dir() {
+        return Ok(true);
+    }
+
+    match on_conflict {
+        CopyConflict::Skip => Ok(false),
+        CopyConflict::Error => Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", to.display()),
+        )),
+        CopyConflict::Overwrite => {
+            if existing.is_dir() {
+                return Err(io::Error::new(
+                    io::ErrorKind::AlreadyExists,
+                    format!("cannot overwrite directory {} with a file", to.display()),
+                ));
+            }
+            fs::remove_file(to)?;
+            Ok(true)
+        }
+    }
+}
+
+fn copy_symlink(entry: &CopyEntry) -> io::Result<()> {
+    let target = fs::read_link(&entry.from)?;
+
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, &entry.to)
+    }
+    #[cfg(windows)]
+    {
+        if fs::metadata(&entry.from).is_ok_and(|m| m.is_dir()) {
+            std::os::windows::fs::symlink_dir(target, &entry.to)
+        } else {
+            std::os::windows::fs::symlink_file(target, &entry.to)
+        }
+    }
+}
+
+fn copy_timestamps(entry: &CopyEntry) -> io::Result<()> {
+    let atime = FileTime::from_last_access_time(&entry.metadata);
+    let mtime = FileTime::from_last_modification_time(&entry.metadata);
+    match entry.kind {
+        CopyKind::Symlink => filetime::set_symlink_file_times(&entry.to, atime, mtime),
+        _ => filetime::set_file_times(&entry.to, atime, mtime),
+    }
+}
+
+fn copy_entries(
+    entries: &[CopyEntry],
+    options: &CopyOptions,
+    on_progress: &Channel<CopyProgress>,
+) -> io::Result<()> {
+    let mut progress = CopyProgress {
+        files_done: 0,
+        files_total: entries
+            .iter()
+            .filter(|e| !matches!(e.kind, CopyKind::Dir))
+            .count() as u64,
+        bytes_done: 0,
+        bytes_total: entries
+            .iter()
+            .filter(|e| matches!(e.kind, CopyKind::File))
+            .map(|e| e.metadata.len())
+            .sum(),
+        current_path: PathBuf::new(),
+    };
+
+    let mut skipped_dirs: Vec<&Path> = Vec::new();
+    let mut copied_dirs = Vec::new();
+
+    for entry in entries {
+        if skipped_dirs.iter().any(|dir| entry.from.starts_with(dir)) {
+            continue;
+        }
+
+        let copy = resolve_conflict(
+            &entry.to,
+            options.on_conflict,
+            matches!(entry.kind, CopyKind::Dir),
+        )
+        .map_err(|e| io::Error::new(e.kind(), format!("{}: {e}", entry.to.display())))?;
+
+        match entry.kind {
+            CopyKind::Dir => {
+                if !copy {
+                    skipped_dirs.push(&entry.from);
+                    continue;
+                }
+                if !entry.to.is_dir() {
+                    fs::create_dir(&entry.to)?;
+                }
+                copied_dirs.push(entry);
+                continue;
+            }
+            CopyKind::File if copy => {
+                if options.preserve_permissions {
+                    fs::copy(&entry.from, &entry.to)?;
+                } else {
+                    io::copy(&mut File::open(&entry.from)?, &mut File::create(&entry.to)?)?;
+                }
+            }
+            CopyKind::Symlink if copy => copy_symlink(entry)?,
+            _ => (),
+        }
+
+        if copy && options.preserve_timestamps {
+            copy_timestamps(entry)?;
+        }
+
+        progress.files_done += 1;
+        if let CopyKind::File = entry.kind {
+            progress.bytes_done += entry.metadata.len();
+        }
+        progress.current_path.clone_from(&entry.from);
+        let _ = on_progress.send(progress.clone());
+    }
+
+    // directories are updated last, children first,
+    // so copying their content does not change their timestamps or fails on read-only permissions
+    for entry in copied_dirs.into_iter().rev() {
+        if options.preserve_timestamps {
+            copy_timestamps(entry)?;
+        }
+        if options.preserve_permissions {
+            fs::set_permissions(&entry.to, entry.metadata.permissions())?;
+        }
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn copy<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    from_path: SafeFilePath,
+    to_path: SafeFilePath,
+    options: Option<CopyOptions>,
+    on_progress: Channel<CopyProgress>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let resolved_from_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        from_path,
+        options.from_path_base_dir,
+    )?;
+    let resolved_to_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        to_path,
+        options.to_path_base_dir,
+    )?;
+
+    if resolved_to_path.starts_with(&resolved_from_path) {
+        return Err(format!(
+            "cannot copy path: {} into itself: {}",
+            resolved_from_path.display(),
+            resolved_to_path.display()
+        )
+        .into());
+    }
+
+    let scope = resolve_scope(&webview, &global_scope, &command_scope)?;
+    let mut entries = Vec::new();
+    plan(
+        &scope,
+        &resolved_from_path,
+        &resolved_to_path,
+        &options,
+        &mut Vec::new(),
+        &mut entries,
+    )?;
+
+    copy_entries(&entries, &options, &on_progress).map_err(|e| {
+        format!(
+            "failed to copy path: {}, to path: {} with error: {e}",
+            resolved_from_path.display(),
+            resolved_to_path.display()
+        )
+    })?;
+
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    use std::os::unix::fs::symlink;
+
+    use tauri::{
+        test::{mock_builder, mock_context, noop_assets},
+        utils::config::FsScope,
+    };
+
+    fn scope(allow: &Path, deny: Vec<PathBuf>) -> Scope {
+        let app = mock_builder().build(mock_context(noop_assets())).unwrap();
+        Scope::new(
+            &app,
+            &FsScope::Scope {
+                allow: vec![allow.join("**")],
+                deny,
+                require_literal_leading_dot: None,
+            },
+        )
+        .unwrap()
+    }
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().simple().to_string());
+        fs::create_dir_all(dir.join("from/nested")).unwrap();
+        dunce::canonicalize(dir).unwrap()
+    }
+
+    #[test]
+    fn symlink_loops_are_rejected() {
+        let dir = temp_dir();
+        symlink(dir.join("from"), dir.join("from/nested/loop")).unwrap();
+        let options = CopyOptions {
+            follow_symlinks: true,
+            ..Default::default()
+        };
+
+        let result = plan(
+            &scope(&dir, Vec::new()),
+            &dir.join("from"),
+            &dir.join("to"),
+            &options,
+            &mut Vec::new(),
+            &mut Vec::new(),
+        );
+        assert!(
+            matches!(&result, Err(Error::Io(e)) if e.to_string().contains("symlink loop")),
+            "{result:?}"
+        );
+
+        // without following symlinks the link itself is copied
+        let mut entries = Vec::new();
+        plan(
+            &scope(&dir, Vec::new()),
+            &dir.join("from"),
+            &dir.join("to"),
+            &CopyOptions::default(),
+            &mut Vec::new(),
+            &mut entries,
+        )
+        .unwrap();
+        assert!(entries
+            .iter()
+            .any(|e| e.from.ends_with("loop") && matches!(e.kind, CopyKind::Symlink)));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn symlink_targets_outside_the_scope_are_rejected() {
+        let dir = temp_dir();
+        let outside = temp_dir();
+        symlink(outside.join("from"), dir.join("from/nested/link")).unwrap();
+
+        let mut entries = Vec::new();
+        let result = plan(
+            &scope(&dir, Vec::new()),
+            &dir.join("from"),
+            &dir.join("to"),
+            &CopyOptions::default(),
+            &mut Vec::new(),
+            &mut entries,
+        );
+        assert!(
+            matches!(&result, Err(Error::PathForbidden(path)) if *path == dir.join("from/nested/link")),
+            "{result:?}"
+        );
+        assert!(!entries.iter().any(|e| e.from.ends_with("link")));
+
+        // a denied target inside the allowed directory is rejected too
+        fs::remove_file(dir.join("from/nested/link")).unwrap();
+        symlink(dir.join("secret"), dir.join("from/nested/link")).unwrap();
+        let result = plan(
+            &scope(&dir, vec![dir.join("secret")]),
+            &dir.join("from"),
+            &dir.join("to"),
+            &CopyOptions::default(),
+            &mut Vec::new(),
+            &mut Vec::new(),
+        );
+        assert!(matches!(result, Err(Error::PathForbidden(_))));
+
+        fs::remove_dir_all(&dir).unwrap();
+        fs::remove_dir_all(&outside).unwrap();
+    }
+}
@@ -0,0 +1,340 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    scope::fs::Scope,
+    Runtime, Webview,
+};
+
+use std::{
+    collections::HashSet,
+    fs::{self, Metadata},
+    io,
+    path::Path,
+};
+
+use crate::{
+    commands::{resolve_path, resolve_scope, BaseOptions, CommandResult},
+    scope::Entry,
+    SafeFilePath,
+};
+
+/// Number of entries visited between two progress messages.
+const PROGRESS_INTERVAL: u64 = 1000;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirSizeOptions {
+    #[serde(flatten)]
+    base: BaseOptions,
+    /// Count the size of files with several hard links once. Only supported on Unix.
+    #[serde(default)]
+    count_hard_links_once: bool,
+}
+
+#[derive(Debug, Default, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DirSize {
+    /// Sum of the sizes of all files, in bytes.
+    bytes: u64,
+    files: u64,
+    directories: u64,
+}
+
+struct SizeCounter<'a> {
+    scope: Scope,
+    /// Device and inode of the files with several hard links that were already counted.
+    #[cfg_attr(not(unix), allow(dead_code))]
+    seen: Option<HashSet<(u64, u64)>>,
+    size: DirSize,
+    on_progress: &'a Channel<DirSize>,
+}
+
+impl SizeCounter<'_> {
+    fn visit(&mut self, path: &Path, metadata: &Metadata) {
+        if metadata.is_dir() {
+            // unreadable directories and entries are skipped
+            if let Ok(entries) = fs::read_dir(path) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if !self.scope.is_allowed(&path) {
+                        continue;
+                    }
+                    if let Ok(metadata) = fs::symlink_metadata(&path) {
+                        if metadata.is_dir() {
+                            self.size.directories += 1;
+                        }
+                        self.visit(&path, &metadata);
+                    }
+                }
+            }
+        } else {
+            self.size.files += 1;
+            if self.is_first_link(metadata) {
+                self.size.bytes += metadata.len();
+            }
+            if self.size.files % PROGRESS_INTERVAL == 0 {
+                let _ = self.on_progress.send(self.size.clone());
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    fn is_first_link(&mut self, metadata: &Metadata) -> bool {
+        use std::os::unix::fs::MetadataExt;
+        match &mut self.seen {
+            Some(seen) if metadata.nlink() > 1 => seen.insert((metadata.dev(), metadata.ino())),
+            _ => true,
+        }
+    }
+
+    #[cfg(not(unix))]
+    fn is_first_link(&mut self, _metadata: &Metadata) -> bool {
+        true
+    }
+}
+
+/// Recursively sums the size of the files under `path`, without following symlinks.
+#[tauri::command]
+pub async fn dir_size<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<DirSizeOptions>,
+    on_progress: Channel<DirSize>,
+) -> CommandResult<DirSize> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.base.base_dir,
+    )?;
+
+    let metadata = fs::symlink_metadata(&resolved_path).map_err(|e| {
+        format!(
+            "failed to get metadata of path: {} with error: {e}",
+            resolved_path.display()
+        )
+    })?;
+
+    let mut counter = SizeCounter {
+        scope: resolve_scope(&webview, &global_scope, &command_scope)?,
+        seen: options.count_hard_links_once.then(HashSet::new),
+        size: DirSize::default(),
+        on_progress: &on_progress,
+    };
+    counter.visit(&resolved_path, &metadata);
+    let _ = on_progress.send(counter.size.clone());
+
+    Ok(counter.size)
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FsStats {
+    /// Size of the filesystem in bytes.
+    total: u64,
+    /// Free bytes, including the ones reserved for privileged users.
+    free: u64,
+    /// Free bytes available to the current user.
+    available: u64,
+    /// Type of the filesystem, e.g. `ext4`, `apfs` or `NTFS`, if it could be determined.
+    fs_type: Option<String>,
+}
+
+/// Decodes the octal escapes (`\040` for a space) used in `/proc/self/mounts`.
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
+fn unescape_mount_field(escaped: &[u8]) -> Vec<u8> {
+    let mut unescaped = Vec::with_capacity(escaped.len());
+    let mut i = 0;
+    while i < escaped.len() {
+        let octal = escaped
+            .get(i + 1..i + 4)
+            .filter(|_| escaped[i] == b'\\')
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        match octal {
+            Some(byte) => {
+                unescaped.push(byte);
+                i += 4;
+            }
+            None => {
+                unescaped.push(escaped[i]);
+                i += 1;
+            }
+        }
+    }
+    unescaped
+}
+
+/// Mount points and their filesystem type listed in `/proc/self/mounts`, empty if it is not available.
+#[cfg(all(unix, not(any(target_os = "macos", target_os = "ios"))))]
+pub(crate) fn mounts() -> Vec<(std::path::PathBuf, String)> {
+    use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+    let Ok(mounts) = fs::read("/proc/self/mounts") else {
+        return Vec::new();
+    };
+    mounts
+        .split(|b| *b == b'\n')
+        .filter_map(|line| {
+            let mut fields = line.split(|b| *b == b' ').skip(1);
+            let mount_point = OsString::from_vec(unescape_mount_field(fields.next()?));
+            let fs_type = String::from_utf8_lossy(fields.next()?).into_owned();
+            Some((mount_point.into(), fs_type))
+        })
+        .collect()
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn fs_type(path: &Path, _c_path: &std::ffi::CStr) -> Option<String> {
+    let path = dunce::canonicalize(path).ok()?;
+    // the last matching mount wins when several are stacked on the same point
+    mounts()
+        .into_iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.as_os_str().len())
+        .map(|(_, fs_type)| fs_type)
+}
+
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "freebsd",
+    target_os = "openbsd"
+))]
+fn fs_type(_path: &Path, c_path: &std::ffi::CStr) -> Option<String> {
+    let mut stat = std::mem::MaybeUninit::<libc::statfs>::uninit();
+    // SAFETY: `c_path` is a valid C string and `stat` is only read if the call succeeds.
+    let stat = unsafe {
+        if libc::statfs(c_path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return None;
+        }
+        stat.assume_init()
+    };
+    // SAFETY: `f_fstypename` is a nul terminated string.
+    let name = unsafe { std::ffi::CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+    Some(name.to_string_lossy().into_owned())
+}
+
+#[cfg(all(
+    unix,
+    not(any(
+        target_os = "linux",
+        target_os = "android",
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "freebsd",
+        target_os = "openbsd"
+    ))
+))]
+fn fs_type(_path: &Path, _c_path: &std::ffi::CStr) -> Option<String> {
+    None
+}
+
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+fn stats(path: &Path) -> io::Result<FsStats> {
+    use std::{ffi::CString, os::unix::ffi::OsStrExt};
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let mut stat = std::mem::MaybeUninit::<libc::statvfs>::uninit();
+    // SAFETY: `c_path` is a valid C string and `stat` is only read if the call succeeds.
+    let stat = unsafe {
+        if libc::statvfs(c_path.as_ptr(), stat.as_mut_ptr()) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        stat.assume_init()
+    };
+
+    let block_size = stat.f_frsize as u64;
+    Ok(FsStats {
+        total: stat.f_blocks as u64 * block_size,
+        free: stat.f_bfree as u64 * block_size,
+        available: stat.f_bavail as u64 * block_size,
+        fs_type: fs_type(path, &c_path),
+    })
+}
+
+#[cfg(windows)]
+fn stats(path: &Path) -> io::Result<FsStats> {
+    use std::{os::windows::ffi::OsStrExt, ptr::null_mut};
+    use windows_sys::Win32::Storage::FileSystem::{
+        GetDiskFreeSpaceExW, GetVolumeInformationW, GetVolumePathNameW,
+    };
+
+    const MAX_PATH: usize = 261;
+
+    let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+    let mut root = [0u16; MAX_PATH];
+    // SAFETY: `wide_path` is nul terminated and `root` is as large as the length passed.
+    if unsafe { GetVolumePathNameW(wide_path.as_ptr(), root.as_mut_ptr(), MAX_PATH as u32) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let (mut available, mut total, mut free) = (0, 0, 0);
+    // SAFETY: `root` is a nul terminated volume path.
+    if unsafe { GetDiskFreeSpaceExW(root.as_ptr(), &mut available, &mut total, &mut free) } == 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    let mut fs_name = [0u16; MAX_PATH];
+    // SAFETY: `root` is a nul terminated volume path and `fs_name` is as large as the length passed.
+    let has_fs_name = unsafe {
+        GetVolumeInformationW(
+            root.as_ptr(),
+            null_mut(),
+            0,
+            null_mut(),
+            null_mut(),
+            null_mut(),
+            fs_name.as_mut_ptr(),
+            MAX_PATH as u32,
+        )
+    } != 0;
+    let fs_type = has_fs_name.then(|| {
+        let len = fs_name.iter().position(|c| *c == 0).unwrap_or(MAX_PATH);
+        String::from_utf16_lossy(&fs_name[..len])
+    });
+
+    Ok(FsStats {
+        total,
+        free,
+        available,
+        fs_type,
+    })
+}
+
+/// Returns the size and free space of the filesystem holding `path`.
+#[tauri::command]
+pub async fn statfs<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<FsStats> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+
+    stats(&resolved_path)
+        .map_err(|e| {
+            format!(
+                "failed to get filesystem information of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
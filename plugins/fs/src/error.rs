@@ -24,6 +24,10 @@ pub enum Error {
     #[cfg(feature = "watch")]
     #[error(transparent)]
     Watch(#[from] notify::Error),
+    /// Zip archive error.
+    #[cfg(feature = "archive")]
+    #[error(transparent)]
+    Zip(#[from] zip::result::ZipError),
     #[cfg(target_os = "android")]
     #[error(transparent)]
     PluginInvoke(#[from] tauri::plugin::mobile::PluginInvokeError),
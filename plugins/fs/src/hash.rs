@@ -0,0 +1,229 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use sha2::Digest;
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    Runtime, Webview,
+};
+
+use std::{fmt::Write as _, io::Read};
+
+use crate::{
+    commands::{resolve_file, BaseOptions, CommandResult, OpenOptions},
+    scope::Entry,
+    SafeFilePath,
+};
+
+const BUFFER_SIZE: usize = 64 * 1024;
+/// Minimum number of bytes hashed between two progress messages.
+const PROGRESS_INTERVAL: u64 = 4 * 1024 * 1024;
+
+#[derive(Debug, Default, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HashAlgorithm {
+    #[default]
+    Sha256,
+    Sha1,
+    Blake3,
+    Crc32,
+}
+
+enum Hasher {
+    Sha256(sha2::Sha256),
+    Sha1(sha1::Sha1),
+    Blake3(Box<blake3::Hasher>),
+    Crc32(crc32fast::Hasher),
+}
+
+impl Hasher {
+    fn new(algorithm: HashAlgorithm) -> Self {
+        match algorithm {
+            HashAlgorithm::Sha256 => Self::Sha256(sha2::Sha256::new()),
+            HashAlgorithm::Sha1 => Self::Sha1(sha1::Sha1::new()),
+            HashAlgorithm::Blake3 => Self::Blake3(Box::default()),
+            HashAlgorithm::Crc32 => Self::Crc32(crc32fast::Hasher::new()),
+        }
+    }
+
+    fn update(&mut self, data: &[u8]) {
+        match self {
+            Self::Sha256(h) => h.update(data),
+            Self::Sha1(h) => h.update(data),
+            Self::Blake3(h) => {
+                h.update(data);
+            }
+            Self::Crc32(h) => h.update(data),
+        }
+    }
+
+    /// Returns the digest as a lowercase hex string.
+    fn finalize(self) -> String {
+        let digest = match self {
+            Self::Sha256(h) => h.finalize().to_vec(),
+            Self::Sha1(h) => h.finalize().to_vec(),
+            Self::Blake3(h) => h.finalize().as_bytes().to_vec(),
+            Self::Crc32(h) => h.finalize().to_be_bytes().to_vec(),
+        };
+        digest.iter().fold(String::new(), |mut hex, byte| {
+            let _ = write!(hex, "{byte:02x}");
+            hex
+        })
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashOptions {
+    #[serde(flatten)]
+    base: BaseOptions,
+    #[serde(default)]
+    algorithm: HashAlgorithm,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HashProgress {
+    bytes_done: u64,
+    bytes_total: u64,
+}
+
+fn hash_file<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+    path: SafeFilePath,
+    options: HashOptions,
+    on_progress: &Channel<HashProgress>,
+) -> CommandResult<String> {
+    let (mut file, path) = resolve_file(
+        webview,
+        global_scope,
+        command_scope,
+        path,
+        OpenOptions {
+            base: options.base,
+            options: crate::OpenOptions {
+                read: true,
+                ..Default::default()
+            },
+        },
+    )?;
+
+    let mut progress = HashProgress {
+        bytes_done: 0,
+        bytes_total: file.metadata().map(|m| m.len()).unwrap_or_default(),
+    };
+    let mut last_progress = 0;
+    let mut hasher = Hasher::new(options.algorithm);
+    let mut buffer = vec![0; BUFFER_SIZE];
+
+    loop {
+        let n = file.read(&mut buffer).map_err(|e| {
+            format!(
+                "failed to read file at path: {} with error: {e}",
+                path.display()
+            )
+        })?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buffer[..n]);
+
+        progress.bytes_done += n as u64;
+        if progress.bytes_done - last_progress >= PROGRESS_INTERVAL {
+            last_progress = progress.bytes_done;
+            let _ = on_progress.send(progress.clone());
+        }
+    }
+
+    if last_progress != progress.bytes_done || progress.bytes_done == 0 {
+        let _ = on_progress.send(progress);
+    }
+
+    Ok(hasher.finalize())
+}
+
+#[tauri::command]
+pub async fn hash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<HashOptions>,
+    on_progress: Channel<HashProgress>,
+) -> CommandResult<String> {
+    hash_file(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.unwrap_or_default(),
+        &on_progress,
+    )
+}
+
+#[tauri::command]
+pub async fn verify<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    expected: String,
+    options: Option<HashOptions>,
+    on_progress: Channel<HashProgress>,
+) -> CommandResult<bool> {
+    let digest = hash_file(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.unwrap_or_default(),
+        &on_progress,
+    )?;
+    Ok(digest.eq_ignore_ascii_case(expected.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Hashes `abc` in two updates, like a file read in several chunks.
+    fn hash_abc(algorithm: HashAlgorithm) -> String {
+        let mut hasher = Hasher::new(algorithm);
+        hasher.update(b"a");
+        hasher.update(b"bc");
+        hasher.finalize()
+    }
+
+    #[test]
+    fn sha256_known_vector() {
+        assert_eq!(
+            hash_abc(HashAlgorithm::Sha256),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn sha1_known_vector() {
+        assert_eq!(
+            hash_abc(HashAlgorithm::Sha1),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+    }
+
+    #[test]
+    fn blake3_known_vector() {
+        assert_eq!(
+            hash_abc(HashAlgorithm::Blake3),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+    }
+
+    #[test]
+    fn crc32_known_vector() {
+        assert_eq!(hash_abc(HashAlgorithm::Crc32), "352441c2");
+    }
+}
@@ -11,7 +11,7 @@
     html_favicon_url = "https://github.com/tauri-apps/tauri/raw/dev/app-icon.png"
 )]
 
-use std::io::Read;
+use std::io::{Read, Write};
 
 use serde::Deserialize;
 use tauri::{
@@ -21,17 +21,30 @@ use tauri::{
     AppHandle, DragDropEvent, Manager, RunEvent, Runtime, WindowEvent,
 };
 
+#[cfg(feature = "archive")]
+mod archive;
+mod atomic;
 mod commands;
 mod config;
+mod copy;
 #[cfg(not(target_os = "android"))]
 mod desktop;
+mod disk;
 mod error;
 mod file_path;
+mod hash;
+mod link;
+mod lock;
+mod metadata;
 #[cfg(target_os = "android")]
 mod mobile;
 #[cfg(target_os = "android")]
 mod models;
 mod scope;
+mod stream;
+mod temp;
+mod trash;
+mod walk;
 #[cfg(feature = "watch")]
 mod watcher;
 
@@ -43,6 +56,7 @@ pub use mobile::Fs;
 pub use error::Error;
 pub use scope::{Event as ScopeEvent, Scope};
 
+pub use crate::trash::TrashItem;
 pub use file_path::FilePath;
 pub use file_path::SafeFilePath;
 
@@ -71,6 +85,13 @@ pub struct OpenOptions {
     custom_flags: Option<i32>,
 }
 
+/// Options of [`Fs::write`].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct WriteOptions {
+    /// Sync the file content to disk before returning, so it survives a crash or power loss.
+    pub fsync: bool,
+}
+
 fn default_true() -> bool {
     true
 }
@@ -343,6 +364,82 @@ impl<R: Runtime> Fs<R> {
         .read_to_end(&mut buf)?;
         Ok(buf)
     }
+
+    /// Writes `contents` to the file at `path`, creating it if it does not exist and truncating it otherwise.
+    pub fn write<P: Into<FilePath>, C: AsRef<[u8]>>(
+        &self,
+        path: P,
+        contents: C,
+        options: WriteOptions,
+    ) -> std::io::Result<()> {
+        let mut file = self.open(
+            path,
+            OpenOptions {
+                read: false,
+                write: true,
+                create: true,
+                truncate: true,
+                ..Default::default()
+            },
+        )?;
+        file.write_all(contents.as_ref())?;
+        if options.fsync {
+            file.sync_all()?;
+        }
+        Ok(())
+    }
+
+    /// Atomically replaces the content of the file at `path` with `contents`.
+    ///
+    /// The data is written to a temporary file next to `path`, synced to disk and renamed into place,
+    /// so readers never see a partially written file. The permissions of an existing file are kept.
+    ///
+    /// A symlink at `path` is replaced with a regular file, its target is left untouched.
+    ///
+    /// Only supported for file system paths.
+    pub fn write_atomic<P: Into<FilePath>, C: AsRef<[u8]>>(
+        &self,
+        path: P,
+        contents: C,
+    ) -> std::io::Result<()> {
+        let path = path
+            .into()
+            .into_path()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+        atomic::write_atomic(&path, contents.as_ref(), None)
+    }
+
+    /// Moves the file or directory at `path` to the trash of the current user.
+    ///
+    /// On Linux and the BSDs this follows the freedesktop.org Trash specification,
+    /// using the trash directory of the mount `path` is on.
+    pub fn trash<P: Into<FilePath>>(&self, path: P) -> std::io::Result<()> {
+        let path = path
+            .into()
+            .into_path()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+        trash::move_to_trash(&path)
+    }
+
+    /// Lists the items in the trash of the current user.
+    ///
+    /// Not supported on macOS and mobile.
+    pub fn list_trash(&self) -> std::io::Result<Vec<TrashItem>> {
+        trash::list()
+    }
+
+    /// Moves the trash item identified by `id` back to its original path and returns that path.
+    /// Fails if the original path already exists.
+    ///
+    /// Not supported on macOS and mobile.
+    pub fn restore_from_trash<P: AsRef<std::path::Path>>(
+        &self,
+        id: P,
+    ) -> std::io::Result<std::path::PathBuf> {
+        let item = trash::find(id.as_ref())?;
+        trash::restore(&item)?;
+        Ok(item.original_path)
+    }
 }
 
 // implement ScopeObject here instead of in the scope module because it is also used on the build script
@@ -395,26 +492,60 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
             commands::create,
             commands::open,
             commands::copy_file,
+            temp::create_temp_file,
+            temp::create_temp_dir,
+            copy::copy,
             commands::close,
             commands::mkdir,
             commands::read_dir,
+            walk::walk_dir,
             commands::read,
+            stream::read_stream,
+            stream::read_stream_ack,
             commands::read_file,
             commands::read_text_file,
             commands::read_text_file_lines,
             commands::read_text_file_lines_next,
             commands::remove,
+            trash::trash,
+            trash::list_trash,
+            trash::restore_from_trash,
             commands::rename,
             commands::seek,
+            lock::lock,
+            lock::try_lock,
+            lock::unlock,
             commands::stat,
             commands::lstat,
             commands::fstat,
+            link::symlink,
+            link::hard_link,
+            link::read_link,
+            link::canonicalize,
+            disk::dir_size,
+            disk::statfs,
+            metadata::chmod,
+            metadata::chown,
+            metadata::utimes,
+            metadata::get_xattr,
+            metadata::set_xattr,
+            metadata::list_xattr,
+            metadata::remove_xattr,
             commands::truncate,
             commands::ftruncate,
             commands::write,
+            stream::create_write_stream,
+            stream::write_stream,
+            stream::finish_write_stream,
             commands::write_file,
             commands::write_text_file,
             commands::exists,
+            hash::hash,
+            hash::verify,
+            #[cfg(feature = "archive")]
+            archive::compress,
+            #[cfg(feature = "archive")]
+            archive::extract,
             #[cfg(feature = "watch")]
             watcher::watch,
             #[cfg(feature = "watch")]
@@ -436,15 +567,15 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
             app.manage(Fs(app.clone()));
 
             app.manage(scope);
+            app.manage(temp::TempPaths::default());
             Ok(())
         })
-        .on_event(|app, event| {
-            if let RunEvent::WindowEvent {
+        .on_event(|app, event| match event {
+            RunEvent::WindowEvent {
                 label: _,
                 event: WindowEvent::DragDrop(DragDropEvent::Drop { paths, position: _ }),
                 ..
-            } = event
-            {
+            } => {
                 let scope = app.fs_scope();
                 for path in paths {
                     if path.is_file() {
@@ -454,6 +585,18 @@ pub fn init<R: Runtime>() -> TauriPlugin<R, Option<config::Config>> {
                     }
                 }
             }
+            RunEvent::WindowEvent {
+                label,
+                event: WindowEvent::Destroyed,
+                ..
+            } => {
+                // the webview of a webview window shares its label, the temporary paths of
+                // other webviews are deleted on exit
+                app.state::<temp::TempPaths>()
+                    .retain(|webview| webview != label.as_str());
+            }
+            RunEvent::Exit => app.state::<temp::TempPaths>().retain(|_| false),
+            _ => {}
         })
         .build()
 }
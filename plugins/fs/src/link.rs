@@ -0,0 +1,289 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Deserialize;
+use tauri::{
+    ipc::{CommandScope, GlobalScope},
+    path::BaseDirectory,
+    Manager, Runtime, Webview,
+};
+
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{
+    commands::{resolve_path, resolve_scope, BaseOptions, CommandResult},
+    scope::Entry,
+    Error, SafeFilePath,
+};
+
+/// Resolves `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// Resolves the symlinks of the longest existing ancestor of `path`, then `.` and `..` in the rest,
+/// which is where the system will look for `path` even if it does not exist yet.
+pub(crate) fn real_path(path: &Path) -> PathBuf {
+    for ancestor in path.ancestors() {
+        if let Ok(canonical) = dunce::canonicalize(ancestor) {
+            let rest = path.strip_prefix(ancestor).unwrap_or(Path::new(""));
+            return normalize(&canonical.join(rest));
+        }
+    }
+    normalize(path)
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SymlinkOptions {
+    /// Base directory for `target`, the link points to the absolute path it resolves to.
+    target_base_dir: Option<BaseDirectory>,
+    /// Base directory for `path`.
+    path_base_dir: Option<BaseDirectory>,
+}
+
+fn create_symlink(target: &Path, path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        std::os::unix::fs::symlink(target, path)
+    }
+    #[cfg(windows)]
+    {
+        // relative targets are relative to the directory of the link
+        let resolved = path.parent().unwrap_or(Path::new("")).join(target);
+        if fs::metadata(resolved).is_ok_and(|m| m.is_dir()) {
+            std::os::windows::fs::symlink_dir(target, path)
+        } else {
+            std::os::windows::fs::symlink_file(target, path)
+        }
+    }
+}
+
+/// Creates a symlink at `path` pointing to `target`.
+///
+/// A relative `target` without base directory is kept relative to the directory of the link.
+/// Wherever it points, the target must be allowed by the scope.
+#[tauri::command]
+pub async fn symlink<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    target: SafeFilePath,
+    path: SafeFilePath,
+    options: Option<SymlinkOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.path_base_dir,
+    )?;
+
+    let target = target.into_path()?;
+    let target = match options.target_base_dir {
+        Some(base_dir) => webview.path().resolve(&target, base_dir)?,
+        None => target,
+    };
+
+    // the target is checked where the link resolves it, through the symlinks on the way
+    let parent = resolved_path.parent().unwrap_or(Path::new(""));
+    let resolved_target = real_path(&parent.join(&target));
+    if !resolve_scope(&webview, &global_scope, &command_scope)?.is_allowed(&resolved_target) {
+        return Err(Error::PathForbidden(resolved_target).into());
+    }
+
+    create_symlink(&target, &resolved_path)
+        .map_err(|e| {
+            format!(
+                "failed to create symlink at path: {} to target: {} with error: {e}",
+                resolved_path.display(),
+                target.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardLinkOptions {
+    from_path_base_dir: Option<BaseDirectory>,
+    to_path_base_dir: Option<BaseDirectory>,
+}
+
+/// Creates a hard link at `to_path` to the file at `from_path`.
+#[tauri::command]
+pub async fn hard_link<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    from_path: SafeFilePath,
+    to_path: SafeFilePath,
+    options: Option<HardLinkOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let resolved_from_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        from_path,
+        options.from_path_base_dir,
+    )?;
+    let resolved_to_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        to_path,
+        options.to_path_base_dir,
+    )?;
+
+    // some platforms link the file a symlink points to
+    let real_from_path = real_path(&resolved_from_path);
+    if !resolve_scope(&webview, &global_scope, &command_scope)?.is_allowed(&real_from_path) {
+        return Err(Error::PathForbidden(real_from_path).into());
+    }
+
+    fs::hard_link(&resolved_from_path, &resolved_to_path)
+        .map_err(|e| {
+            format!(
+                "failed to create hard link from path: {}, to path: {} with error: {e}",
+                resolved_from_path.display(),
+                resolved_to_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+/// Resolves to the target of the symlink at `path`, as it is stored in the link.
+#[tauri::command]
+pub async fn read_link<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<PathBuf> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+
+    fs::read_link(&resolved_path)
+        .map_err(|e| {
+            format!(
+                "failed to read link at path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+/// Resolves to the absolute path of `path` with all symlinks resolved.
+///
+/// Fails if that path is not allowed by the scope.
+#[tauri::command]
+pub async fn canonicalize<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<PathBuf> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+
+    let canonical = dunce::canonicalize(&resolved_path).map_err(|e| {
+        format!(
+            "failed to canonicalize path: {} with error: {e}",
+            resolved_path.display()
+        )
+    })?;
+    if !resolve_scope(&webview, &global_scope, &command_scope)?.is_allowed(&canonical) {
+        return Err(Error::PathForbidden(canonical).into());
+    }
+
+    Ok(canonical)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir() -> PathBuf {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().simple().to_string());
+        fs::create_dir_all(dir.join("links")).unwrap();
+        dunce::canonicalize(dir).unwrap()
+    }
+
+    #[test]
+    fn parent_components_are_resolved() {
+        let dir = temp_dir();
+
+        assert_eq!(real_path(&dir.join("links/../target")), dir.join("target"));
+        assert_eq!(
+            real_path(&dir.join("links/../../target")),
+            dir.parent().unwrap().join("target")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlink_ancestors_are_resolved() {
+        let dir = temp_dir();
+        fs::create_dir_all(dir.join("outside/deep")).unwrap();
+        std::os::unix::fs::symlink(dir.join("outside/deep"), dir.join("links/alias")).unwrap();
+
+        assert_eq!(
+            real_path(&dir.join("links/alias/new/file")),
+            dir.join("outside/deep/new/file")
+        );
+        // `..` after a symlink leaves the directory the link points to, not the one holding the link
+        assert_eq!(
+            real_path(&dir.join("links/alias/../secret")),
+            dir.join("outside/secret")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_paths_are_normalized() {
+        let dir = temp_dir();
+
+        assert_eq!(
+            real_path(&dir.join("missing/nested/../file")),
+            dir.join("missing/file")
+        );
+        assert_eq!(
+            real_path(Path::new("missing/../file")),
+            PathBuf::from("file")
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
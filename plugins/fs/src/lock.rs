@@ -0,0 +1,158 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Advisory locks on the files opened with the `open` command.
+//!
+//! Locks use `flock` on Unix, so they only exclude other handles that lock the file too,
+//! and `LockFileEx` on Windows, where they are enforced by the system.
+
+use serde::Deserialize;
+use tauri::{Manager, ResourceId, Runtime, Webview};
+
+use std::{fs::File, io};
+
+use crate::commands::{CommandResult, StdFileResource};
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LockMode {
+    /// Can be held by several handles at once, but not with an exclusive lock.
+    Shared,
+    #[default]
+    Exclusive,
+}
+
+#[cfg(unix)]
+pub(crate) type RawFile = std::os::unix::io::RawFd;
+#[cfg(windows)]
+pub(crate) type RawFile = std::os::windows::io::RawHandle;
+
+pub(crate) fn raw(file: &File) -> RawFile {
+    #[cfg(unix)]
+    {
+        std::os::unix::io::AsRawFd::as_raw_fd(file)
+    }
+    #[cfg(windows)]
+    {
+        std::os::windows::io::AsRawHandle::as_raw_handle(file)
+    }
+}
+
+/// Locks the whole file, waiting for conflicting locks to be released if `wait` is set.
+///
+/// Returns `false` if the file is locked elsewhere and `wait` is not set.
+#[cfg(unix)]
+pub(crate) fn acquire(file: RawFile, mode: LockMode, wait: bool) -> io::Result<bool> {
+    let mut operation = match mode {
+        LockMode::Shared => libc::LOCK_SH,
+        LockMode::Exclusive => libc::LOCK_EX,
+    };
+    if !wait {
+        operation |= libc::LOCK_NB;
+    }
+
+    loop {
+        // SAFETY: `file` is a file descriptor kept open by its resource.
+        if unsafe { libc::flock(file, operation) } == 0 {
+            return Ok(true);
+        }
+        let error = io::Error::last_os_error();
+        match error.raw_os_error() {
+            Some(libc::EWOULDBLOCK) if !wait => return Ok(false),
+            Some(libc::EINTR) => continue,
+            _ => return Err(error),
+        }
+    }
+}
+
+#[cfg(unix)]
+pub(crate) fn release(file: RawFile) -> io::Result<()> {
+    // SAFETY: `file` is a file descriptor kept open by its resource.
+    if unsafe { libc::flock(file, libc::LOCK_UN) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(windows)]
+pub(crate) fn acquire(file: RawFile, mode: LockMode, wait: bool) -> io::Result<bool> {
+    use windows_sys::Win32::{
+        Foundation::ERROR_LOCK_VIOLATION,
+        Storage::FileSystem::{LockFileEx, LOCKFILE_EXCLUSIVE_LOCK, LOCKFILE_FAIL_IMMEDIATELY},
+        System::IO::OVERLAPPED,
+    };
+
+    let mut flags = 0;
+    if mode == LockMode::Exclusive {
+        flags |= LOCKFILE_EXCLUSIVE_LOCK;
+    }
+    if !wait {
+        flags |= LOCKFILE_FAIL_IMMEDIATELY;
+    }
+
+    // SAFETY: an all zero OVERLAPPED locks from offset 0.
+    let mut overlapped: OVERLAPPED = unsafe { std::mem::zeroed() };
+    // SAFETY: `file` is a handle kept open by its resource.
+    if unsafe { LockFileEx(file, flags, 0, u32::MAX, u32::MAX, &mut overlapped) } != 0 {
+        return Ok(true);
+    }
+    let error = io::Error::last_os_error();
+    match error.raw_os_error() {
+        Some(code) if code == ERROR_LOCK_VIOLATION as i32 && !wait => Ok(false),
+        _ => Err(error),
+    }
+}
+
+#[cfg(windows)]
+pub(crate) fn release(file: RawFile) -> io::Result<()> {
+    use windows_sys::Win32::{Storage::FileSystem::UnlockFileEx, System::IO::OVERLAPPED};
+
+    // SAFETY: an all zero OVERLAPPED unlocks from offset 0.
+    let mut overlapped: OVERLAPPED = unsafe { std::mem::zeroed() };
+    // SAFETY: `file` is a handle kept open by its resource.
+    if unsafe { UnlockFileEx(file, 0, u32::MAX, u32::MAX, &mut overlapped) } != 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Locks an open file, waiting until conflicting locks are released.
+///
+/// A lock already held through the same handle is released first.
+#[tauri::command]
+pub async fn lock<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+    mode: Option<LockMode>,
+) -> CommandResult<()> {
+    let file = webview.resources_table().get::<StdFileResource>(rid)?;
+    let mode = mode.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || file.lock(mode, true))
+        .await?
+        .map_err(|e| format!("failed to lock file with error: {e}"))?;
+    Ok(())
+}
+
+/// Locks an open file, resolving to `false` instead of waiting if it is locked elsewhere.
+#[tauri::command]
+pub async fn try_lock<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+    mode: Option<LockMode>,
+) -> CommandResult<bool> {
+    let file = webview.resources_table().get::<StdFileResource>(rid)?;
+    file.lock(mode.unwrap_or_default(), false)
+        .map_err(|e| format!("failed to lock file with error: {e}"))
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn unlock<R: Runtime>(webview: Webview<R>, rid: ResourceId) -> CommandResult<()> {
+    let file = webview.resources_table().get::<StdFileResource>(rid)?;
+    file.unlock()
+        .map_err(|e| format!("failed to unlock file with error: {e}"))
+        .map_err(Into::into)
+}
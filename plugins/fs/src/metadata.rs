@@ -0,0 +1,396 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use filetime::FileTime;
+use serde::Deserialize;
+use tauri::{
+    ipc::{CommandScope, GlobalScope},
+    Runtime, Webview,
+};
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    commands::{resolve_path, resolve_scope, BaseOptions, CommandResult},
+    scope::Entry,
+    Error, SafeFilePath,
+};
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MetadataOptions {
+    #[serde(flatten)]
+    base: BaseOptions,
+    /// Change the file a symlink points to instead of the symlink itself.
+    #[serde(default)]
+    follow_symlinks: bool,
+}
+
+/// Resolves `path` and, when following symlinks, makes sure their target is also allowed by the scope.
+fn resolve<R: Runtime>(
+    webview: &Webview<R>,
+    global_scope: &GlobalScope<Entry>,
+    command_scope: &CommandScope<Entry>,
+    path: SafeFilePath,
+    options: &MetadataOptions,
+) -> CommandResult<PathBuf> {
+    let resolved_path = resolve_path(
+        webview,
+        global_scope,
+        command_scope,
+        path,
+        options.base.base_dir,
+    )?;
+
+    if options.follow_symlinks
+        && fs::symlink_metadata(&resolved_path).is_ok_and(|m| m.file_type().is_symlink())
+    {
+        let target = dunce::canonicalize(&resolved_path).map_err(|e| {
+            format!(
+                "failed to resolve symlink at path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })?;
+        if !resolve_scope(webview, global_scope, command_scope)?.is_allowed(&target) {
+            return Err(Error::PathForbidden(target).into());
+        }
+    }
+
+    Ok(resolved_path)
+}
+
+#[cfg(not(unix))]
+fn unsupported() -> io::Error {
+    io::Error::new(io::ErrorKind::Unsupported, "not supported on this platform")
+}
+
+fn set_mode(path: &Path, mode: u32, follow_symlinks: bool) -> io::Result<()> {
+    // the setuid, setgid and sticky bits could be used to escalate privileges
+    if mode & !0o777 != 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("mode {mode:#o} has bits other than the permission bits 0o777"),
+        ));
+    }
+
+    let metadata = fs::symlink_metadata(path)?;
+    if metadata.file_type().is_symlink() && !follow_symlinks {
+        // most platforms cannot change the mode of the symlink itself
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "cannot change the mode of a symlink without following it",
+        ));
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(mode))
+    }
+    #[cfg(not(unix))]
+    {
+        // only the write permission of the owner maps to the read-only attribute
+        let mut permissions = fs::metadata(path)?.permissions();
+        permissions.set_readonly(mode & 0o200 == 0);
+        fs::set_permissions(path, permissions)
+    }
+}
+
+#[tauri::command]
+pub async fn chmod<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    mode: u32,
+    options: Option<MetadataOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve(&webview, &global_scope, &command_scope, path, &options)?;
+
+    set_mode(&resolved_path, mode, options.follow_symlinks)
+        .map_err(|e| {
+            format!(
+                "failed to change the mode of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn set_owner(
+    path: &Path,
+    uid: Option<u32>,
+    gid: Option<u32>,
+    follow_symlinks: bool,
+) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        if follow_symlinks {
+            std::os::unix::fs::chown(path, uid, gid)
+        } else {
+            std::os::unix::fs::lchown(path, uid, gid)
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        Err(unsupported())
+    }
+}
+
+/// Changes the owner and group of `path`, `None` keeps the current value.
+#[tauri::command]
+pub async fn chown<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    uid: Option<u32>,
+    gid: Option<u32>,
+    options: Option<MetadataOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve(&webview, &global_scope, &command_scope, path, &options)?;
+
+    set_owner(&resolved_path, uid, gid, options.follow_symlinks)
+        .map_err(|e| {
+            format!(
+                "failed to change the owner of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+/// Converts milliseconds since the Unix epoch, like JavaScript dates, to a [`FileTime`].
+fn from_msec(msec: i64) -> FileTime {
+    FileTime::from_unix_time(
+        msec.div_euclid(1000),
+        (msec.rem_euclid(1000) * 1_000_000) as u32,
+    )
+}
+
+fn set_times(
+    path: &Path,
+    atime: Option<i64>,
+    mtime: Option<i64>,
+    follow_symlinks: bool,
+) -> io::Result<()> {
+    let metadata = if follow_symlinks {
+        fs::metadata(path)?
+    } else {
+        fs::symlink_metadata(path)?
+    };
+    let atime = atime
+        .map(from_msec)
+        .unwrap_or_else(|| FileTime::from_last_access_time(&metadata));
+    let mtime = mtime
+        .map(from_msec)
+        .unwrap_or_else(|| FileTime::from_last_modification_time(&metadata));
+
+    if follow_symlinks {
+        filetime::set_file_times(path, atime, mtime)
+    } else {
+        filetime::set_symlink_file_times(path, atime, mtime)
+    }
+}
+
+/// Sets the access and modification times of `path` in milliseconds since the Unix epoch,
+/// `None` keeps the current value.
+#[tauri::command]
+pub async fn utimes<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    atime: Option<i64>,
+    mtime: Option<i64>,
+    options: Option<MetadataOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve(&webview, &global_scope, &command_scope, path, &options)?;
+
+    set_times(&resolved_path, atime, mtime, options.follow_symlinks)
+        .map_err(|e| {
+            format!(
+                "failed to set the times of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[cfg(unix)]
+mod xattrs {
+    use std::{io, path::Path};
+
+    pub fn get(path: &Path, name: &str, follow_symlinks: bool) -> io::Result<Option<Vec<u8>>> {
+        if follow_symlinks {
+            xattr::get_deref(path, name)
+        } else {
+            xattr::get(path, name)
+        }
+    }
+
+    pub fn set(path: &Path, name: &str, value: &[u8], follow_symlinks: bool) -> io::Result<()> {
+        if follow_symlinks {
+            xattr::set_deref(path, name, value)
+        } else {
+            xattr::set(path, name, value)
+        }
+    }
+
+    pub fn list(path: &Path, follow_symlinks: bool) -> io::Result<Vec<String>> {
+        let names = if follow_symlinks {
+            xattr::list_deref(path)?
+        } else {
+            xattr::list(path)?
+        };
+        Ok(names
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect())
+    }
+
+    pub fn remove(path: &Path, name: &str, follow_symlinks: bool) -> io::Result<()> {
+        if follow_symlinks {
+            xattr::remove_deref(path, name)
+        } else {
+            xattr::remove(path, name)
+        }
+    }
+}
+
+#[cfg(not(unix))]
+#[allow(unused_variables)]
+mod xattrs {
+    use std::{io, path::Path};
+
+    pub fn get(path: &Path, name: &str, follow_symlinks: bool) -> io::Result<Option<Vec<u8>>> {
+        Err(super::unsupported())
+    }
+
+    pub fn set(path: &Path, name: &str, value: &[u8], follow_symlinks: bool) -> io::Result<()> {
+        Err(super::unsupported())
+    }
+
+    pub fn list(path: &Path, follow_symlinks: bool) -> io::Result<Vec<String>> {
+        Err(super::unsupported())
+    }
+
+    pub fn remove(path: &Path, name: &str, follow_symlinks: bool) -> io::Result<()> {
+        Err(super::unsupported())
+    }
+}
+
+/// Reads the extended attribute `name` of `path`, resolving to `None` if it is not set.
+#[tauri::command]
+pub async fn get_xattr<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    name: String,
+    options: Option<MetadataOptions>,
+) -> CommandResult<Option<Vec<u8>>> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve(&webview, &global_scope, &command_scope, path, &options)?;
+
+    xattrs::get(&resolved_path, &name, options.follow_symlinks)
+        .map_err(|e| {
+            format!(
+                "failed to get extended attribute {name} of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn set_xattr<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    name: String,
+    value: Vec<u8>,
+    options: Option<MetadataOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve(&webview, &global_scope, &command_scope, path, &options)?;
+
+    xattrs::set(&resolved_path, &name, &value, options.follow_symlinks)
+        .map_err(|e| {
+            format!(
+                "failed to set extended attribute {name} of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn list_xattr<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<MetadataOptions>,
+) -> CommandResult<Vec<String>> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve(&webview, &global_scope, &command_scope, path, &options)?;
+
+    xattrs::list(&resolved_path, options.follow_symlinks)
+        .map_err(|e| {
+            format!(
+                "failed to list extended attributes of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[tauri::command]
+pub async fn remove_xattr<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    name: String,
+    options: Option<MetadataOptions>,
+) -> CommandResult<()> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve(&webview, &global_scope, &command_scope, path, &options)?;
+
+    xattrs::remove(&resolved_path, &name, options.follow_symlinks)
+        .map_err(|e| {
+            format!(
+                "failed to remove extended attribute {name} of path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn special_mode_bits_are_rejected() {
+        let path = std::env::temp_dir().join(uuid::Uuid::new_v4().simple().to_string());
+        fs::write(&path, "").unwrap();
+
+        for mode in [0o4755, 0o2755, 0o1777] {
+            let error = set_mode(&path, mode, false).unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        }
+        set_mode(&path, 0o644, false).unwrap();
+
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,337 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Deserialize;
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope, InvokeResponseBody},
+    Manager, Resource, ResourceId, Runtime, Webview,
+};
+
+use std::{
+    borrow::Cow,
+    fs::File,
+    io::{Read, Seek, SeekFrom, Write},
+    sync::Mutex,
+};
+
+use tokio::sync::Semaphore;
+
+use crate::{
+    atomic::AtomicFile,
+    commands::{
+        check_atomic_write, resolve_file, resolve_path, BaseOptions, CommandResult, OpenOptions,
+        StdFileResource, WriteFileOptions,
+    },
+    scope::Entry,
+    SafeFilePath,
+};
+
+const DEFAULT_CHUNK_SIZE: usize = 1024 * 1024;
+const DEFAULT_HIGH_WATER_MARK: usize = 4;
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadStreamOptions {
+    /// Offset of the first byte to read, defaults to the current position of the file.
+    start: Option<u64>,
+    /// Offset of the byte after the last byte to read, defaults to the end of the file.
+    end: Option<u64>,
+    chunk_size: Option<usize>,
+    /// Number of chunks that can be sent before the consumer acknowledges them.
+    high_water_mark: Option<usize>,
+}
+
+/// Sends the content of an open file in chunks over `on_chunk`,
+/// waiting for [`read_stream_ack`] once `high_water_mark` chunks are unacknowledged.
+///
+/// Resolves to the number of bytes sent once the stream is done.
+#[tauri::command]
+pub async fn read_stream<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+    options: Option<ReadStreamOptions>,
+    on_chunk: Channel<InvokeResponseBody>,
+) -> CommandResult<u64> {
+    let options = options.unwrap_or_default();
+    let file = webview.resources_table().get::<StdFileResource>(rid)?;
+    let credits = file
+        .start_stream(
+            options
+                .high_water_mark
+                .unwrap_or(DEFAULT_HIGH_WATER_MARK)
+                .max(1),
+        )
+        .ok_or("the file is already being streamed")?;
+
+    let result = send_chunks(&file, &credits, &options, &on_chunk).await;
+    file.end_stream();
+    result
+}
+
+async fn send_chunks(
+    file: &StdFileResource,
+    credits: &Semaphore,
+    options: &ReadStreamOptions,
+    on_chunk: &Channel<InvokeResponseBody>,
+) -> CommandResult<u64> {
+    let start = file
+        .with_lock(|mut file| match options.start {
+            Some(start) => file.seek(SeekFrom::Start(start)),
+            None => file.stream_position(),
+        })
+        .map_err(|e| format!("failed to seek file with error: {e}"))?;
+
+    let chunk_size = options.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE).max(1);
+    let mut remaining = options.end.map(|end| end.saturating_sub(start));
+    let mut sent = 0;
+
+    loop {
+        let len = remaining.map_or(chunk_size, |r| r.min(chunk_size as u64) as usize);
+        if len == 0 {
+            break;
+        }
+        match credits.acquire().await {
+            Ok(permit) => permit.forget(),
+            // the stream was cancelled or the file closed
+            Err(_) => break,
+        }
+
+        let mut chunk = vec![0; len];
+        let nread = file
+            .with_lock(|mut file| file.read(&mut chunk))
+            .map_err(|e| format!("failed to read bytes from file with error: {e}"))?;
+        if nread == 0 {
+            break;
+        }
+        chunk.truncate(nread);
+        on_chunk.send(InvokeResponseBody::Raw(chunk))?;
+
+        sent += nread as u64;
+        remaining = remaining.map(|r| r - nread as u64);
+    }
+
+    Ok(sent)
+}
+
+/// Acknowledges a chunk sent by [`read_stream`], or stops the stream if `cancel` is set.
+#[tauri::command]
+pub fn read_stream_ack<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+    cancel: Option<bool>,
+) -> CommandResult<()> {
+    let file = webview.resources_table().get::<StdFileResource>(rid)?;
+    if cancel.unwrap_or_default() {
+        file.end_stream();
+    } else if let Some(credits) = file.stream() {
+        credits.add_permits(1);
+    }
+    Ok(())
+}
+
+enum WriteTarget {
+    File(File),
+    Atomic(AtomicFile),
+}
+
+impl WriteTarget {
+    fn file(&self) -> &File {
+        match self {
+            Self::File(file) => file,
+            Self::Atomic(file) => file.file(),
+        }
+    }
+}
+
+/// A file written in chunks, see [`create_write_stream`].
+///
+/// Closing the resource without calling [`finish_write_stream`] discards an atomic write.
+struct WriteStreamResource {
+    target: Mutex<Option<WriteTarget>>,
+    fsync: bool,
+}
+
+impl WriteStreamResource {
+    fn with_lock<R, F: FnOnce(&File) -> std::io::Result<R>>(&self, f: F) -> std::io::Result<R> {
+        match self.target.lock().unwrap().as_ref() {
+            Some(target) => f(target.file()),
+            None => Err(std::io::Error::other(
+                "the write stream is already finished",
+            )),
+        }
+    }
+
+    fn finish(&self) -> std::io::Result<()> {
+        match self.target.lock().unwrap().take() {
+            Some(WriteTarget::File(mut file)) => {
+                file.flush()?;
+                if self.fsync {
+                    file.sync_all()?;
+                }
+                Ok(())
+            }
+            Some(WriteTarget::Atomic(file)) => file.commit(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Resource for WriteStreamResource {}
+
+/// Opens `path` for writing in chunks with [`write_stream`].
+///
+/// With the `atomic` option the chunks are written to a temporary file
+/// that only replaces `path` in [`finish_write_stream`].
+#[tauri::command]
+pub async fn create_write_stream<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<WriteFileOptions>,
+) -> CommandResult<ResourceId> {
+    let (target, fsync) = match options {
+        Some(opts) if opts.atomic => {
+            let path = resolve_path(
+                &webview,
+                &global_scope,
+                &command_scope,
+                path,
+                opts.base.base_dir,
+            )?;
+            check_atomic_write(&path, &opts)?;
+            let file = AtomicFile::create(&path, opts.mode).map_err(|e| {
+                format!(
+                    "failed to create file at path: {} with error: {e}",
+                    path.display()
+                )
+            })?;
+            (WriteTarget::Atomic(file), true)
+        }
+        opts => {
+            let fsync = opts.as_ref().is_some_and(|o| o.fsync);
+            let (file, _path) = resolve_file(
+                &webview,
+                &global_scope,
+                &command_scope,
+                path,
+                match opts {
+                    Some(opts) => OpenOptions {
+                        base: opts.base,
+                        options: crate::OpenOptions {
+                            write: true,
+                            create: opts.create,
+                            truncate: !opts.append,
+                            append: opts.append,
+                            create_new: opts.create_new,
+                            mode: opts.mode,
+                            ..Default::default()
+                        },
+                    },
+                    None => OpenOptions {
+                        base: BaseOptions { base_dir: None },
+                        options: crate::OpenOptions {
+                            write: true,
+                            create: true,
+                            truncate: true,
+                            ..Default::default()
+                        },
+                    },
+                },
+            )?;
+            (WriteTarget::File(file), fsync)
+        }
+    };
+
+    let rid = webview.resources_table().add(WriteStreamResource {
+        target: Mutex::new(Some(target)),
+        fsync,
+    });
+    Ok(rid)
+}
+
+/// Appends the raw request body to a write stream, the stream is identified by the `rid` header.
+#[tauri::command]
+pub async fn write_stream<R: Runtime>(
+    webview: Webview<R>,
+    request: tauri::ipc::Request<'_>,
+) -> CommandResult<()> {
+    let data = match request.body() {
+        tauri::ipc::InvokeBody::Raw(data) => Cow::Borrowed(data),
+        tauri::ipc::InvokeBody::Json(serde_json::Value::Array(data)) => Cow::Owned(
+            data.iter()
+                .flat_map(|v| v.as_number().and_then(|v| v.as_u64().map(|v| v as u8)))
+                .collect(),
+        ),
+        _ => return Err(anyhow::anyhow!("unexpected invoke body").into()),
+    };
+    let rid: ResourceId = request
+        .headers()
+        .get("rid")
+        .and_then(|rid| rid.to_str().ok())
+        .and_then(|rid| rid.parse().ok())
+        .ok_or_else(|| anyhow::anyhow!("missing write stream rid"))?;
+
+    let stream = webview.resources_table().get::<WriteStreamResource>(rid)?;
+    stream
+        .with_lock(|mut file| file.write_all(&data))
+        .map_err(|e| format!("failed to write bytes to stream with error: {e}"))
+        .map_err(Into::into)
+}
+
+/// Flushes a write stream, renames the temporary file of an atomic stream and closes it.
+#[tauri::command]
+pub async fn finish_write_stream<R: Runtime>(
+    webview: Webview<R>,
+    rid: ResourceId,
+) -> CommandResult<()> {
+    let stream = webview.resources_table().take::<WriteStreamResource>(rid)?;
+    stream
+        .finish()
+        .map_err(|e| format!("failed to finish write stream with error: {e}"))
+        .map_err(Into::into)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Arc;
+
+    #[test]
+    fn stream_ends_relative_to_current_position() {
+        let path = std::env::temp_dir().join(uuid::Uuid::new_v4().simple().to_string());
+        std::fs::write(&path, (0..100).collect::<Vec<u8>>()).unwrap();
+        let mut file = File::open(&path).unwrap();
+        file.seek(SeekFrom::Start(10)).unwrap();
+        let file = StdFileResource::new(file);
+
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let on_chunk = Channel::new({
+            let received = received.clone();
+            move |body| {
+                if let InvokeResponseBody::Raw(chunk) = body {
+                    received.lock().unwrap().extend(chunk);
+                }
+                Ok(())
+            }
+        });
+        let options = ReadStreamOptions {
+            end: Some(20),
+            chunk_size: Some(4),
+            ..Default::default()
+        };
+        let sent = tauri::async_runtime::block_on(send_chunks(
+            &file,
+            &Semaphore::new(10),
+            &options,
+            &on_chunk,
+        ))
+        .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(sent, 10);
+        assert_eq!(*received.lock().unwrap(), (10..20).collect::<Vec<u8>>());
+    }
+}
@@ -0,0 +1,260 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::{Deserialize, Serialize};
+use tauri::{Manager, Resource, ResourceId, Runtime, Webview};
+
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use crate::commands::CommandResult;
+
+struct TempEntry {
+    path: PathBuf,
+    is_dir: bool,
+}
+
+/// Temporary files and directories created by each webview, keyed by the webview label.
+///
+/// Their paths are allowed in the scope of the webview that created them until they are removed.
+#[derive(Default, Clone)]
+pub(crate) struct TempPaths(Arc<Mutex<HashMap<String, Vec<TempEntry>>>>);
+
+impl TempPaths {
+    fn add(&self, webview: &str, path: &Path, is_dir: bool) {
+        self.0
+            .lock()
+            .unwrap()
+            .entry(webview.to_string())
+            .or_default()
+            .push(TempEntry {
+                path: path.to_path_buf(),
+                is_dir,
+            });
+    }
+
+    /// Scope patterns allowing the temporary paths of `webview`.
+    ///
+    /// The paths are escaped so glob metacharacters in their names only match themselves.
+    pub(crate) fn allowed(&self, webview: &str) -> Vec<PathBuf> {
+        let paths = self.0.lock().unwrap();
+        let Some(entries) = paths.get(webview) else {
+            return Vec::new();
+        };
+        let mut allowed = Vec::new();
+        for entry in entries {
+            let escaped = PathBuf::from(glob::Pattern::escape(&entry.path.to_string_lossy()));
+            if entry.is_dir {
+                allowed.push(escaped.join("**"));
+            }
+            allowed.push(escaped);
+        }
+        allowed
+    }
+
+    /// Forgets and deletes a temporary path of `webview`, doing nothing if it was already removed.
+    fn remove(&self, webview: &str, path: &Path) {
+        let removed = {
+            let mut paths = self.0.lock().unwrap();
+            let Some(entries) = paths.get_mut(webview) else {
+                return;
+            };
+            let removed = entries
+                .iter()
+                .position(|entry| entry.path == path)
+                .map(|i| entries.swap_remove(i));
+            if entries.is_empty() {
+                paths.remove(webview);
+            }
+            removed
+        };
+        if let Some(entry) = removed {
+            delete(&entry);
+        }
+    }
+
+    /// Deletes the temporary paths of the webviews for which `keep` returns `false`.
+    pub(crate) fn retain(&self, mut keep: impl FnMut(&str) -> bool) {
+        let removed: Vec<TempEntry> = {
+            let mut paths = self.0.lock().unwrap();
+            let labels: Vec<String> = paths
+                .keys()
+                .filter(|l| !keep(l.as_str()))
+                .cloned()
+                .collect();
+            labels
+                .iter()
+                .filter_map(|label| paths.remove(label))
+                .flatten()
+                .collect()
+        };
+        for entry in &removed {
+            delete(entry);
+        }
+    }
+}
+
+fn delete(entry: &TempEntry) {
+    // errors are ignored since the path may have been removed by the frontend
+    let _ = if entry.is_dir {
+        fs::remove_dir_all(&entry.path)
+    } else {
+        fs::remove_file(&entry.path)
+    };
+}
+
+/// A temporary file or directory, deleted when the resource is closed or dropped with its webview.
+struct TempPathResource {
+    paths: TempPaths,
+    webview: String,
+    path: PathBuf,
+}
+
+impl Resource for TempPathResource {}
+
+impl Drop for TempPathResource {
+    fn drop(&mut self) {
+        self.paths.remove(&self.webview, &self.path);
+    }
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TempOptions {
+    /// Start of the file name, before a random part.
+    #[serde(default)]
+    prefix: String,
+    /// End of the file name, after a random part.
+    #[serde(default)]
+    suffix: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TempPath {
+    rid: ResourceId,
+    path: PathBuf,
+}
+
+/// Returns a unique path in the temporary directory of the system.
+fn temp_path<R: Runtime>(webview: &Webview<R>, options: &TempOptions) -> CommandResult<PathBuf> {
+    if options
+        .prefix
+        .chars()
+        .chain(options.suffix.chars())
+        .any(std::path::is_separator)
+    {
+        return Err("the prefix and suffix of a temporary path cannot contain a separator".into());
+    }
+    let name = format!(
+        "{}{}{}",
+        options.prefix,
+        uuid::Uuid::new_v4().simple(),
+        options.suffix
+    );
+    Ok(webview.path().temp_dir()?.join(name))
+}
+
+fn create_file(path: &Path) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path).map(drop)
+}
+
+fn create_dir(path: &Path) -> io::Result<()> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::DirBuilderExt;
+        fs::DirBuilder::new().mode(0o700).create(path)
+    }
+    #[cfg(not(unix))]
+    {
+        fs::create_dir(path)
+    }
+}
+
+fn add_resource<R: Runtime>(webview: &Webview<R>, path: PathBuf, is_dir: bool) -> TempPath {
+    let paths = webview.state::<TempPaths>().inner().clone();
+    paths.add(webview.label(), &path, is_dir);
+    let rid = webview.resources_table().add(TempPathResource {
+        paths,
+        webview: webview.label().to_string(),
+        path: path.clone(),
+    });
+    TempPath { rid, path }
+}
+
+/// Creates an empty file in the temporary directory, only accessible to the current user on Unix.
+///
+/// The file is allowed in the scope of the webview until the resource is closed,
+/// the webview is destroyed or the app exits, at which point it is deleted.
+#[tauri::command]
+pub async fn create_temp_file<R: Runtime>(
+    webview: Webview<R>,
+    options: Option<TempOptions>,
+) -> CommandResult<TempPath> {
+    let path = temp_path(&webview, &options.unwrap_or_default())?;
+    create_file(&path).map_err(|e| {
+        format!(
+            "failed to create temporary file at path: {} with error: {e}",
+            path.display()
+        )
+    })?;
+    Ok(add_resource(&webview, path, false))
+}
+
+/// Creates an empty directory in the temporary directory, only accessible to the current user on Unix.
+///
+/// The directory and its content are allowed in the scope of the webview until the resource is closed,
+/// the webview is destroyed or the app exits, at which point it is deleted.
+#[tauri::command]
+pub async fn create_temp_dir<R: Runtime>(
+    webview: Webview<R>,
+    options: Option<TempOptions>,
+) -> CommandResult<TempPath> {
+    let path = temp_path(&webview, &options.unwrap_or_default())?;
+    create_dir(&path).map_err(|e| {
+        format!(
+            "failed to create temporary directory at path: {} with error: {e}",
+            path.display()
+        )
+    })?;
+    Ok(add_resource(&webview, path, true))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_allowed(paths: &TempPaths, path: &Path) -> bool {
+        paths.allowed("main").iter().any(|p| {
+            glob::Pattern::new(&p.to_string_lossy())
+                .unwrap()
+                .matches_path(path)
+        })
+    }
+
+    #[test]
+    fn glob_metacharacters_are_escaped() {
+        let dir = std::env::temp_dir();
+        let paths = TempPaths::default();
+        paths.add("main", &dir.join("a*[b]?"), false);
+        paths.add("main", &dir.join("*"), true);
+
+        assert!(is_allowed(&paths, &dir.join("a*[b]?")));
+        assert!(!is_allowed(&paths, &dir.join("aXbY")));
+        assert!(is_allowed(&paths, &dir.join("*").join("file")));
+        assert!(!is_allowed(&paths, &dir.join("other")));
+        assert!(!is_allowed(&paths, &dir.join("other").join("file")));
+    }
+}
@@ -0,0 +1,198 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+use tauri::{
+    ipc::{CommandScope, GlobalScope},
+    Runtime, Webview,
+};
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    commands::{resolve_path, resolve_scope, BaseOptions, CommandResult},
+    scope::Entry,
+    Error, SafeFilePath,
+};
+
+#[cfg(all(
+    unix,
+    not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+))]
+mod freedesktop;
+#[cfg(windows)]
+mod windows;
+
+/// An entry of the trash.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrashItem {
+    /// Identifies the item inside the trash to restore it, its path on Linux and the BSDs.
+    pub id: PathBuf,
+    /// Path the item was deleted from.
+    pub original_path: PathBuf,
+    /// Deletion time in milliseconds since the Unix epoch, if known.
+    pub deleted_at: Option<u64>,
+    pub is_directory: bool,
+    /// Path of the `.trashinfo` file describing the item.
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    #[serde(skip)]
+    pub(crate) info_path: PathBuf,
+}
+
+#[cfg(any(target_os = "macos", target_os = "ios", target_os = "android"))]
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the trash is not supported on this platform",
+    )
+}
+
+/// Moves `path` to the trash of the current user.
+pub(crate) fn move_to_trash(path: &Path) -> io::Result<()> {
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    {
+        freedesktop::trash(path)
+    }
+    #[cfg(any(windows, target_os = "macos"))]
+    {
+        ::trash::delete(path).map_err(|e| io::Error::other(e.to_string()))
+    }
+    #[cfg(any(target_os = "ios", target_os = "android"))]
+    {
+        let _ = path;
+        Err(unsupported())
+    }
+}
+
+/// Lists the items in the trash of the current user.
+///
+/// Not supported on macOS and mobile.
+pub(crate) fn list() -> io::Result<Vec<TrashItem>> {
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    {
+        freedesktop::list()
+    }
+    #[cfg(windows)]
+    {
+        windows::list()
+    }
+    #[cfg(any(target_os = "macos", target_os = "ios", target_os = "android"))]
+    {
+        Err(unsupported())
+    }
+}
+
+/// Moves an item of the trash back to its original path, failing if that path already exists.
+///
+/// Not supported on macOS and mobile.
+pub(crate) fn restore(item: &TrashItem) -> io::Result<()> {
+    #[cfg(all(
+        unix,
+        not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+    ))]
+    {
+        freedesktop::restore(item)
+    }
+    #[cfg(windows)]
+    {
+        windows::restore(item)
+    }
+    #[cfg(any(target_os = "macos", target_os = "ios", target_os = "android"))]
+    {
+        let _ = item;
+        Err(unsupported())
+    }
+}
+
+/// Finds the item of the trash identified by `id`.
+pub(crate) fn find(id: &Path) -> io::Result<TrashItem> {
+    list()?
+        .into_iter()
+        .find(|item| item.id == id)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not in the trash", id.display()),
+            )
+        })
+}
+
+#[tauri::command]
+pub async fn trash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<BaseOptions>,
+) -> CommandResult<()> {
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.and_then(|o| o.base_dir),
+    )?;
+
+    move_to_trash(&resolved_path)
+        .map_err(|e| {
+            format!(
+                "failed to move path: {} to the trash with error: {e}",
+                resolved_path.display()
+            )
+        })
+        .map_err(Into::into)
+}
+
+/// Lists the items of the trash that were deleted from a path allowed by the scope.
+#[tauri::command]
+pub async fn list_trash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+) -> CommandResult<Vec<TrashItem>> {
+    let scope = resolve_scope(&webview, &global_scope, &command_scope)?;
+    let items = list().map_err(|e| format!("failed to list the trash with error: {e}"))?;
+    Ok(items
+        .into_iter()
+        .filter(|item| scope.is_allowed(&item.original_path))
+        .collect())
+}
+
+/// Restores an item of the trash, resolving to its original path.
+#[tauri::command]
+pub async fn restore_from_trash<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    id: PathBuf,
+) -> CommandResult<PathBuf> {
+    let item = find(&id).map_err(|e| format!("failed to restore from the trash: {e}"))?;
+
+    let scope = resolve_scope(&webview, &global_scope, &command_scope)?;
+    if !scope.is_allowed(&item.original_path) {
+        return Err(Error::PathForbidden(item.original_path).into());
+    }
+
+    restore(&item).map_err(|e| {
+        format!(
+            "failed to restore path: {} from the trash with error: {e}",
+            item.original_path.display()
+        )
+    })?;
+
+    Ok(item.original_path)
+}
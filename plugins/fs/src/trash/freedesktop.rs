@@ -0,0 +1,396 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Implementation of the [freedesktop.org Trash specification](https://specifications.freedesktop.org/trash-spec/trashspec-latest.html).
+
+use percent_encoding::{percent_decode, percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use time::{format_description, PrimitiveDateTime};
+
+use std::{
+    collections::HashSet,
+    ffi::{OsStr, OsString},
+    fs::{self, DirBuilder, File},
+    io::{self, Write},
+    os::unix::{
+        ffi::{OsStrExt, OsStringExt},
+        fs::{DirBuilderExt, MetadataExt, PermissionsExt},
+    },
+    path::{Path, PathBuf},
+};
+
+use super::TrashItem;
+
+/// Characters kept as is in the `Path` key of `.trashinfo` files.
+const PATH_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'/')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'.')
+    .remove(b'~');
+
+/// Format of the `DeletionDate` key, in the local time zone without offset.
+const DELETION_DATE_FORMAT: &str = "[year]-[month]-[day]T[hour]:[minute]:[second]";
+
+const INFO_EXTENSION: &str = ".trashinfo";
+
+/// A trash directory with its `files` and `info` sub directories.
+struct TrashDir {
+    path: PathBuf,
+    /// The directory original paths are relative to, `None` for the home trash which uses absolute paths.
+    top_dir: Option<PathBuf>,
+}
+
+impl TrashDir {
+    fn files(&self) -> PathBuf {
+        self.path.join("files")
+    }
+
+    fn info(&self) -> PathBuf {
+        self.path.join("info")
+    }
+
+    fn create(&self) -> io::Result<()> {
+        let mut builder = DirBuilder::new();
+        builder.recursive(true).mode(0o700);
+        builder.create(self.files())?;
+        builder.create(self.info())
+    }
+}
+
+fn uid() -> u32 {
+    // SAFETY: `getuid` is always successful and has no side effects.
+    unsafe { libc::getuid() }
+}
+
+/// `$XDG_DATA_HOME/Trash`, defaulting to `~/.local/share/Trash`.
+fn home_trash() -> io::Result<TrashDir> {
+    let data_home = match std::env::var_os("XDG_DATA_HOME").map(PathBuf::from) {
+        Some(dir) if dir.is_absolute() => dir,
+        _ => std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".local/share"))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "HOME is not set"))?,
+    };
+    Ok(TrashDir {
+        path: data_home.join("Trash"),
+        top_dir: None,
+    })
+}
+
+/// Returns the top directory of the mount containing `path`.
+fn mount_top_dir(path: &Path, dev: u64) -> PathBuf {
+    let mut top_dir = path;
+    while let Some(parent) = top_dir.parent() {
+        match fs::symlink_metadata(parent) {
+            Ok(metadata) if metadata.dev() == dev => top_dir = parent,
+            _ => break,
+        }
+    }
+    top_dir.to_path_buf()
+}
+
+/// Returns `$top_dir/.Trash/$uid` if the administrator created a valid shared `.Trash` directory.
+fn shared_trash(top_dir: &Path) -> Option<TrashDir> {
+    let shared = top_dir.join(".Trash");
+    let metadata = fs::symlink_metadata(&shared).ok()?;
+    // must be a real directory with the sticky bit set
+    if !metadata.is_dir() || metadata.permissions().mode() & 0o1000 == 0 {
+        return None;
+    }
+    Some(TrashDir {
+        path: shared.join(uid().to_string()),
+        top_dir: Some(top_dir.to_path_buf()),
+    })
+}
+
+fn user_trash(top_dir: &Path) -> TrashDir {
+    TrashDir {
+        path: top_dir.join(format!(".Trash-{}", uid())),
+        top_dir: Some(top_dir.to_path_buf()),
+    }
+}
+
+/// Checks that an existing per mount trash directory is a real directory owned by the user.
+fn is_valid_trash(trash: &TrashDir) -> bool {
+    match fs::symlink_metadata(&trash.path) {
+        Ok(metadata) => metadata.is_dir() && metadata.uid() == uid(),
+        Err(e) => e.kind() == io::ErrorKind::NotFound,
+    }
+}
+
+/// Picks the trash directory for a file on device `dev`, so it can be renamed instead of copied.
+fn trash_for(path: &Path, dev: u64, home: TrashDir) -> io::Result<TrashDir> {
+    home.create()?;
+    if fs::metadata(&home.path)?.dev() == dev {
+        return Ok(home);
+    }
+
+    let top_dir = mount_top_dir(path, dev);
+    for trash in [shared_trash(&top_dir), Some(user_trash(&top_dir))]
+        .into_iter()
+        .flatten()
+    {
+        if is_valid_trash(&trash) && trash.create().is_ok() {
+            return Ok(trash);
+        }
+    }
+
+    Err(io::Error::other(format!(
+        "no trash directory available on the mount of {}",
+        path.display()
+    )))
+}
+
+/// The current time in the local time zone, formatted for the `DeletionDate` key.
+///
+/// `localtime_r` is used since the `time` crate cannot get the local offset in a multi threaded process.
+fn local_now() -> io::Result<String> {
+    // SAFETY: `time` accepts a null pointer and `localtime_r` only writes to `tm`.
+    let tm = unsafe {
+        let now = libc::time(std::ptr::null_mut());
+        let mut tm: libc::tm = std::mem::zeroed();
+        if libc::localtime_r(&now, &mut tm).is_null() {
+            return Err(io::Error::last_os_error());
+        }
+        tm
+    };
+    Ok(format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        tm.tm_year + 1900,
+        tm.tm_mon + 1,
+        tm.tm_mday,
+        tm.tm_hour,
+        tm.tm_min,
+        tm.tm_sec
+    ))
+}
+
+/// Converts a `DeletionDate` in the local time zone to milliseconds since the Unix epoch.
+fn local_to_unix_millis(date: PrimitiveDateTime) -> Option<u64> {
+    // SAFETY: an all zero `tm` is valid.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    tm.tm_year = date.year() - 1900;
+    tm.tm_mon = i32::from(u8::from(date.month())) - 1;
+    tm.tm_mday = i32::from(date.day());
+    tm.tm_hour = i32::from(date.hour());
+    tm.tm_min = i32::from(date.minute());
+    tm.tm_sec = i32::from(date.second());
+    // let the system find out whether daylight saving time applies
+    tm.tm_isdst = -1;
+    // SAFETY: `mktime` only reads and normalizes `tm`.
+    let secs = unsafe { libc::mktime(&mut tm) };
+    u64::try_from(secs).ok().map(|secs| secs * 1000)
+}
+
+/// Creates the `.trashinfo` file of a new item, picking a name that is not used in the trash yet.
+fn claim_name(trash: &TrashDir, file_name: &OsStr) -> io::Result<(OsString, PathBuf, File)> {
+    for i in 1u32.. {
+        let mut name = file_name.to_os_string();
+        if i > 1 {
+            name.push(format!(".{i}"));
+        }
+        if fs::symlink_metadata(trash.files().join(&name)).is_ok() {
+            continue;
+        }
+
+        let mut info_name = name.clone();
+        info_name.push(INFO_EXTENSION);
+        let info_path = trash.info().join(info_name);
+        match File::options()
+            .write(true)
+            .create_new(true)
+            .open(&info_path)
+        {
+            Ok(file) => return Ok((name, info_path, file)),
+            Err(e) if e.kind() == io::ErrorKind::AlreadyExists => continue,
+            Err(e) => return Err(e),
+        }
+    }
+    unreachable!()
+}
+
+pub(super) fn trash(path: &Path) -> io::Result<()> {
+    trash_with_home(path, home_trash()?)
+}
+
+/// Moves `path` to the trash, using `home` as the home trash directory.
+fn trash_with_home(path: &Path, home: TrashDir) -> io::Result<()> {
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(path)
+    };
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "cannot trash this path"))?;
+    let metadata = fs::symlink_metadata(&path)?;
+
+    let trash = trash_for(&path, metadata.dev(), home)?;
+    let original_path = match &trash.top_dir {
+        Some(top_dir) => path.strip_prefix(top_dir).unwrap_or(&path),
+        None => &path,
+    };
+    let deletion_date = local_now()?;
+
+    let (name, info_path, mut info) = claim_name(&trash, file_name)?;
+    let result = write!(
+        info,
+        "[Trash Info]\nPath={}\nDeletionDate={deletion_date}\n",
+        percent_encode(original_path.as_os_str().as_bytes(), PATH_ENCODE_SET)
+    )
+    .and_then(|_| info.sync_all())
+    .and_then(|_| fs::rename(&path, trash.files().join(name)));
+
+    if result.is_err() {
+        let _ = fs::remove_file(info_path);
+    }
+    result
+}
+
+/// All trash directories of the user that exist, starting with `home`.
+fn trash_dirs(home: Option<TrashDir>) -> Vec<TrashDir> {
+    let mut dirs: Vec<TrashDir> = home.into_iter().collect();
+    for (mount_point, _) in crate::disk::mounts() {
+        dirs.extend(shared_trash(&mount_point));
+        dirs.push(user_trash(&mount_point));
+    }
+    let mut seen = HashSet::new();
+    dirs.retain(|trash| trash.info().is_dir() && seen.insert(trash.path.clone()));
+    dirs
+}
+
+fn parse_info(trash: &TrashDir, content: &str) -> Option<(PathBuf, Option<u64>)> {
+    let mut lines = content.lines().map(str::trim);
+    if lines.next()? != "[Trash Info]" {
+        return None;
+    }
+
+    let mut original_path = None;
+    let mut deleted_at = None;
+    for line in lines {
+        if let Some(path) = line.strip_prefix("Path=") {
+            let path = PathBuf::from(OsString::from_vec(
+                percent_decode(path.as_bytes()).collect(),
+            ));
+            original_path = Some(match &trash.top_dir {
+                Some(top_dir) if path.is_relative() => top_dir.join(path),
+                _ => path,
+            });
+        } else if let Some(date) = line.strip_prefix("DeletionDate=") {
+            deleted_at = format_description::parse(DELETION_DATE_FORMAT)
+                .ok()
+                .and_then(|format| PrimitiveDateTime::parse(date, &format).ok())
+                .and_then(local_to_unix_millis);
+        }
+    }
+
+    original_path.map(|path| (path, deleted_at))
+}
+
+pub(super) fn list() -> io::Result<Vec<TrashItem>> {
+    list_with_home(home_trash().ok())
+}
+
+/// Lists the items of all trash directories, using `home` as the home trash directory.
+fn list_with_home(home: Option<TrashDir>) -> io::Result<Vec<TrashItem>> {
+    let mut items = Vec::new();
+
+    for trash in trash_dirs(home) {
+        let files = trash.files();
+        // unreadable trash directories are skipped
+        let Ok(entries) = fs::read_dir(trash.info()) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let info_path = entry.path();
+            let Some(name) = info_path
+                .file_name()
+                .and_then(|name| name.as_bytes().strip_suffix(INFO_EXTENSION.as_bytes()))
+            else {
+                continue;
+            };
+            let id = files.join(OsStr::from_bytes(name));
+            let Ok(metadata) = fs::symlink_metadata(&id) else {
+                // orphaned `.trashinfo` file
+                continue;
+            };
+            let Some((original_path, deleted_at)) = fs::read_to_string(&info_path)
+                .ok()
+                .and_then(|content| parse_info(&trash, &content))
+            else {
+                continue;
+            };
+
+            items.push(TrashItem {
+                id,
+                original_path,
+                deleted_at,
+                is_directory: metadata.is_dir(),
+                info_path,
+            });
+        }
+    }
+
+    Ok(items)
+}
+
+pub(super) fn restore(item: &TrashItem) -> io::Result<()> {
+    if fs::symlink_metadata(&item.original_path).is_ok() {
+        return Err(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", item.original_path.display()),
+        ));
+    }
+    if let Some(parent) = item.original_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::rename(&item.id, &item.original_path)?;
+    fs::remove_file(&item.info_path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    #[test]
+    fn trash_and_restore() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().simple().to_string());
+        fs::create_dir(&dir).unwrap();
+        // the home trash is passed in, changing `XDG_DATA_HOME` would race with the other tests
+        let home = || TrashDir {
+            path: dir.join("data/Trash"),
+            top_dir: None,
+        };
+        let path = dir.join("file.txt");
+        fs::write(&path, "content").unwrap();
+
+        trash_with_home(&path, home()).unwrap();
+        assert!(!path.exists());
+
+        let item = list_with_home(Some(home()))
+            .unwrap()
+            .into_iter()
+            .find(|item| item.original_path == path)
+            .unwrap();
+        assert!(item.id.starts_with(dir.join("data/Trash/files")));
+        assert!(!item.is_directory);
+        // the deletion date is written and read back in the local time zone
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64;
+        assert!(now.abs_diff(item.deleted_at.unwrap()) < 5000);
+
+        restore(&item).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "content");
+        assert!(!item.info_path.exists());
+        assert!(list_with_home(Some(home()))
+            .unwrap()
+            .iter()
+            .all(|i| i.original_path != path));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! Listing and restoring the items of the Recycle Bin, through the `trash` crate.
+
+use ::trash::{os_limited, TrashItemSize};
+
+use std::{
+    io,
+    path::{Path, PathBuf},
+};
+
+use super::TrashItem;
+
+fn to_io_error(error: ::trash::Error) -> io::Error {
+    match error {
+        ::trash::Error::RestoreCollision { path, .. } => io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("{} already exists", path.display()),
+        ),
+        error => io::Error::other(error.to_string()),
+    }
+}
+
+pub(super) fn list() -> io::Result<Vec<TrashItem>> {
+    let items = os_limited::list().map_err(to_io_error)?;
+    Ok(items
+        .into_iter()
+        .map(|item| TrashItem {
+            id: PathBuf::from(&item.id),
+            original_path: item.original_path(),
+            deleted_at: u64::try_from(item.time_deleted)
+                .ok()
+                .map(|secs| secs * 1000),
+            is_directory: matches!(
+                os_limited::metadata(&item).map(|metadata| metadata.size),
+                Ok(TrashItemSize::Entries(_))
+            ),
+        })
+        .collect())
+}
+
+pub(super) fn restore(item: &TrashItem) -> io::Result<()> {
+    // the Recycle Bin restores items through its own representation of them
+    let item = os_limited::list()
+        .map_err(to_io_error)?
+        .into_iter()
+        .find(|i| Path::new(&i.id) == item.id)
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} is not in the trash", item.id.display()),
+            )
+        })?;
+    os_limited::restore_all([item]).map_err(to_io_error)
+}
@@ -0,0 +1,338 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+use tauri::{
+    ipc::{Channel, CommandScope, GlobalScope},
+    path::BaseDirectory,
+    scope::fs::Scope,
+    Runtime, Webview,
+};
+
+use std::{
+    fs::{self, Metadata},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    commands::{get_stat, parse_patterns, resolve_path, resolve_scope, CommandResult, FileInfo},
+    scope::Entry,
+    FsExt, SafeFilePath,
+};
+
+const DEFAULT_CHUNK_SIZE: usize = 256;
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WalkSort {
+    Name,
+    Size,
+    Modified,
+}
+
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkDirOptions {
+    base_dir: Option<BaseDirectory>,
+    max_depth: Option<usize>,
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    include_hidden: Option<bool>,
+    #[serde(default)]
+    stat: bool,
+    sort: Option<WalkSort>,
+    chunk_size: Option<usize>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WalkEntry {
+    path: PathBuf,
+    name: String,
+    depth: usize,
+    is_directory: bool,
+    is_file: bool,
+    is_symlink: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    info: Option<FileInfo>,
+}
+
+struct Walker<'a> {
+    root: &'a Path,
+    scope: Scope,
+    max_depth: Option<usize>,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    match_options: MatchOptions,
+    include_hidden: bool,
+    stat: bool,
+    sort: Option<WalkSort>,
+    chunk_size: usize,
+    chunk: Vec<WalkEntry>,
+    count: usize,
+    on_entries: Channel<Vec<WalkEntry>>,
+}
+
+impl Walker<'_> {
+    fn walk(&mut self, dir: &Path, depth: usize) -> std::io::Result<()> {
+        let mut children: Vec<(PathBuf, Metadata)> = Vec::new();
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            // `symlink_metadata` does not fail on broken symlinks
+            if let Ok(metadata) = fs::symlink_metadata(&path) {
+                children.push((path, metadata));
+            }
+        }
+
+        match self.sort {
+            Some(WalkSort::Name) => children.sort_by(|(a, _), (b, _)| a.cmp(b)),
+            Some(WalkSort::Size) => children.sort_by_key(|(_, m)| m.len()),
+            Some(WalkSort::Modified) => children.sort_by_key(|(_, m)| m.modified().ok()),
+            None => (),
+        }
+
+        for (path, metadata) in children {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            if !self.include_hidden && name.starts_with('.') {
+                continue;
+            }
+            if !self.scope.is_allowed(&path) {
+                continue;
+            }
+
+            let relative = path.strip_prefix(self.root).unwrap_or(&path);
+            if self
+                .exclude
+                .iter()
+                .any(|p| p.matches_path_with(relative, self.match_options))
+            {
+                continue;
+            }
+
+            let is_dir = metadata.is_dir();
+            if self.include.is_empty()
+                || self
+                    .include
+                    .iter()
+                    .any(|p| p.matches_path_with(relative, self.match_options))
+            {
+                self.push(WalkEntry {
+                    is_directory: is_dir,
+                    is_file: metadata.is_file(),
+                    is_symlink: metadata.file_type().is_symlink(),
+                    info: self.stat.then(|| get_stat(metadata)),
+                    path: path.clone(),
+                    name,
+                    depth,
+                });
+            }
+
+            if is_dir && self.max_depth.map_or(true, |max| depth < max) {
+                // unreadable sub directories are skipped instead of failing the whole walk
+                let _ = self.walk(&path, depth + 1);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn push(&mut self, entry: WalkEntry) {
+        self.chunk.push(entry);
+        self.count += 1;
+        if self.chunk.len() >= self.chunk_size {
+            self.flush();
+        }
+    }
+
+    fn flush(&mut self) {
+        if !self.chunk.is_empty() {
+            let chunk = std::mem::replace(&mut self.chunk, Vec::with_capacity(self.chunk_size));
+            let _ = self.on_entries.send(chunk);
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn walk_dir<R: Runtime>(
+    webview: Webview<R>,
+    global_scope: GlobalScope<Entry>,
+    command_scope: CommandScope<Entry>,
+    path: SafeFilePath,
+    options: Option<WalkDirOptions>,
+    on_entries: Channel<Vec<WalkEntry>>,
+) -> CommandResult<usize> {
+    let options = options.unwrap_or_default();
+    let resolved_path = resolve_path(
+        &webview,
+        &global_scope,
+        &command_scope,
+        path,
+        options.base_dir,
+    )?;
+
+    // hidden files are only listed by default if the scope globs match them
+    let require_literal_leading_dot = webview
+        .fs_scope()
+        .require_literal_leading_dot
+        .unwrap_or(cfg!(unix));
+    let include_hidden = options
+        .include_hidden
+        .unwrap_or(!require_literal_leading_dot);
+
+    let mut walker = Walker {
+        root: &resolved_path,
+        scope: resolve_scope(&webview, &global_scope, &command_scope)?,
+        max_depth: options.max_depth,
+        include: parse_patterns(&options.include)?,
+        exclude: parse_patterns(&options.exclude)?,
+        match_options: MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: !include_hidden,
+        },
+        include_hidden,
+        stat: options.stat,
+        sort: options.sort,
+        chunk_size: options.chunk_size.unwrap_or(DEFAULT_CHUNK_SIZE).max(1),
+        chunk: Vec::new(),
+        count: 0,
+        on_entries,
+    };
+
+    if walker.max_depth != Some(0) {
+        walker.walk(&resolved_path, 1).map_err(|e| {
+            format!(
+                "failed to read directory at path: {} with error: {e}",
+                resolved_path.display()
+            )
+        })?;
+    }
+    walker.flush();
+
+    Ok(walker.count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use tauri::{
+        ipc::InvokeResponseBody,
+        test::{mock_builder, mock_context, noop_assets},
+        utils::config::FsScope,
+    };
+
+    /// Walks `dir` with the given scope and sort, returning the walked paths relative to `dir`.
+    fn walk(dir: &Path, allow: Vec<PathBuf>, deny: Vec<PathBuf>, sort: WalkSort) -> Vec<String> {
+        let app = mock_builder().build(mock_context(noop_assets())).unwrap();
+        let scope = Scope::new(
+            &app,
+            &FsScope::Scope {
+                allow,
+                deny,
+                require_literal_leading_dot: None,
+            },
+        )
+        .unwrap();
+
+        let entries = Arc::new(Mutex::new(Vec::new()));
+        let on_entries = Channel::new({
+            let entries = entries.clone();
+            move |body| {
+                if let InvokeResponseBody::Json(chunk) = body {
+                    let chunk: Vec<serde_json::Value> = serde_json::from_str(&chunk).unwrap();
+                    entries.lock().unwrap().extend(chunk);
+                }
+                Ok(())
+            }
+        });
+        let mut walker = Walker {
+            root: dir,
+            scope,
+            max_depth: None,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            match_options: MatchOptions::new(),
+            include_hidden: true,
+            stat: false,
+            sort: Some(sort),
+            chunk_size: 2,
+            chunk: Vec::new(),
+            count: 0,
+            on_entries,
+        };
+        walker.walk(dir, 1).unwrap();
+        walker.flush();
+
+        let entries = entries.lock().unwrap();
+        assert_eq!(entries.len(), walker.count);
+        entries
+            .iter()
+            .map(|entry| {
+                Path::new(entry["path"].as_str().unwrap())
+                    .strip_prefix(dir)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect()
+    }
+
+    #[test]
+    fn entries_are_sorted_per_directory() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().simple().to_string());
+        fs::create_dir_all(dir.join("b")).unwrap();
+        let dir = dunce::canonicalize(dir).unwrap();
+        fs::write(dir.join("c.txt"), "c").unwrap();
+        fs::write(dir.join("a.txt"), "aaaa").unwrap();
+        fs::write(dir.join("b/z.txt"), "zz").unwrap();
+        fs::write(dir.join("b/y.txt"), "yyy").unwrap();
+
+        // the children of a directory are listed right after it, in their own order
+        let by_name = walk(&dir, vec![dir.join("**")], Vec::new(), WalkSort::Name);
+        assert_eq!(by_name, ["a.txt", "b", "b/y.txt", "b/z.txt", "c.txt"]);
+
+        let by_size = walk(
+            &dir.join("b"),
+            vec![dir.join("**")],
+            Vec::new(),
+            WalkSort::Size,
+        );
+        assert_eq!(by_size, ["z.txt", "y.txt"]);
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn entries_outside_the_scope_are_skipped() {
+        let dir = std::env::temp_dir().join(uuid::Uuid::new_v4().simple().to_string());
+        fs::create_dir_all(dir.join("public/nested")).unwrap();
+        fs::create_dir_all(dir.join("secret")).unwrap();
+        let dir = dunce::canonicalize(dir).unwrap();
+        fs::write(dir.join("public/nested/file.txt"), "").unwrap();
+        fs::write(dir.join("secret/key.txt"), "").unwrap();
+        fs::write(dir.join("denied.txt"), "").unwrap();
+
+        let walked = walk(
+            &dir,
+            vec![dir.join("**")],
+            vec![dir.join("secret"), dir.join("denied.txt")],
+            WalkSort::Name,
+        );
+        assert_eq!(
+            walked,
+            ["public", "public/nested", "public/nested/file.txt"]
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -2,17 +2,23 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use glob::Pattern;
+use notify::{
+    event::{ModifyKind, RenameMode},
+    Config, Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher,
+};
 use notify_debouncer_full::{new_debouncer, DebounceEventResult, Debouncer, FileIdMap};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
 use tauri::{
     ipc::{Channel, CommandScope, GlobalScope},
     path::BaseDirectory,
+    scope::fs::Scope,
     Manager, Resource, ResourceId, Runtime, Webview,
 };
 
 use std::{
-    path::PathBuf,
+    path::{Path, PathBuf},
     sync::{
         mpsc::{channel, Receiver},
         Mutex,
@@ -22,7 +28,7 @@ use std::{
 };
 
 use crate::{
-    commands::{resolve_path, CommandResult},
+    commands::{parse_patterns, resolve_path, resolve_scope, CommandResult},
     scope::Entry,
     SafeFilePath,
 };
@@ -51,23 +57,166 @@ enum WatcherKind {
     Watcher(RecommendedWatcher),
 }
 
-fn watch_raw(on_event: Channel<Event>, rx: Receiver<notify::Result<Event>>) {
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WatchEventKindFilter {
+    Access,
+    Create,
+    Modify,
+    Remove,
+    Other,
+}
+
+impl WatchEventKindFilter {
+    fn matches(&self, kind: &EventKind) -> bool {
+        matches!(
+            (self, kind),
+            (Self::Access, EventKind::Access(_))
+                | (Self::Create, EventKind::Create(_))
+                | (Self::Modify, EventKind::Modify(_))
+                | (Self::Remove, EventKind::Remove(_))
+                | (Self::Other, EventKind::Any | EventKind::Other)
+        )
+    }
+}
+
+/// A message sent to the frontend by a watcher that reports errors.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum WatcherEvent {
+    /// A file system event.
+    Event { event: Event },
+    /// The watcher failed, it keeps running and may recover.
+    Error {
+        message: String,
+        paths: Vec<PathBuf>,
+    },
+    /// Events were missed, for instance because the OS event queue overflowed,
+    /// so the watched paths must be rescanned.
+    Rescan { paths: Vec<PathBuf> },
+}
+
+/// Drops the events and paths the webview is not allowed to see or is not interested in.
+struct EventFilter {
+    scope: Scope,
+    roots: Vec<PathBuf>,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    kinds: Option<Vec<WatchEventKindFilter>>,
+}
+
+impl EventFilter {
+    fn filter(&self, mut event: Event) -> Option<Event> {
+        if let Some(kinds) = &self.kinds {
+            if !kinds.iter().any(|k| k.matches(&event.kind)) {
+                return None;
+            }
+        }
+
+        if event.paths.is_empty() {
+            return Some(event);
+        }
+        if event.kind == EventKind::Modify(ModifyKind::Name(RenameMode::Both)) {
+            // a rename from or to a path that is filtered out is reported as a one sided rename
+            if let [from, to] = &event.paths[..] {
+                match (self.is_allowed(from), self.is_allowed(to)) {
+                    (true, false) => {
+                        event.kind = EventKind::Modify(ModifyKind::Name(RenameMode::From))
+                    }
+                    (false, true) => {
+                        event.kind = EventKind::Modify(ModifyKind::Name(RenameMode::To))
+                    }
+                    _ => {}
+                }
+            }
+        }
+        event.paths.retain(|path| self.is_allowed(path));
+        (!event.paths.is_empty()).then_some(event)
+    }
+
+    /// Converts a notify event result into the message sent to the frontend, if any.
+    fn message(&self, event: notify::Result<Event>) -> Option<WatcherEvent> {
+        match event {
+            Ok(mut event) if event.need_rescan() => {
+                event.paths.retain(|path| self.is_allowed(path));
+                Some(WatcherEvent::Rescan { paths: event.paths })
+            }
+            Ok(event) => self
+                .filter(event)
+                .map(|event| WatcherEvent::Event { event }),
+            Err(mut error) => {
+                // the error message must not include the paths that are filtered out
+                let mut paths = std::mem::take(&mut error.paths);
+                paths.retain(|path| self.scope.is_allowed(path));
+                Some(WatcherEvent::Error {
+                    message: error.to_string(),
+                    paths,
+                })
+            }
+        }
+    }
+
+    fn is_allowed(&self, path: &Path) -> bool {
+        if !self.scope.is_allowed(path) {
+            return false;
+        }
+
+        let relative = self
+            .roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+        !self.exclude.iter().any(|p| p.matches_path(relative))
+            && (self.include.is_empty() || self.include.iter().any(|p| p.matches_path(relative)))
+    }
+}
+
+/// Sends the filtered events of a watcher to the frontend.
+struct EventSender {
+    filter: EventFilter,
+    on_event: Channel<JsonValue>,
+    /// Send [`WatcherEvent`]s instead of plain events, which drop errors and rescan notices.
+    report_errors: bool,
+}
+
+impl EventSender {
+    fn send(&self, event: notify::Result<Event>) {
+        let message = if self.report_errors {
+            self.filter.message(event).map(serde_json::to_value)
+        } else {
+            event
+                .ok()
+                .and_then(|event| self.filter.filter(event))
+                .map(serde_json::to_value)
+        };
+        if let Some(Ok(message)) = message {
+            let _ = self.on_event.send(message);
+        }
+    }
+}
+
+fn watch_raw(rx: Receiver<notify::Result<Event>>, sender: EventSender) {
     spawn(move || {
         while let Ok(event) = rx.recv() {
-            if let Ok(event) = event {
-                // TODO: Should errors be emitted too?
-                let _ = on_event.send(event);
-            }
+            sender.send(event);
         }
     });
 }
 
-fn watch_debounced(on_event: Channel<Event>, rx: Receiver<DebounceEventResult>) {
+fn watch_debounced(rx: Receiver<DebounceEventResult>, sender: EventSender) {
     spawn(move || {
-        while let Ok(Ok(events)) = rx.recv() {
-            for event in events {
-                // TODO: Should errors be emitted too?
-                let _ = on_event.send(event.event);
+        while let Ok(result) = rx.recv() {
+            match result {
+                Ok(events) => {
+                    for event in events {
+                        sender.send(Ok(event.event));
+                    }
+                }
+                Err(errors) => {
+                    for error in errors {
+                        sender.send(Err(error));
+                    }
+                }
             }
         }
     });
@@ -79,6 +228,14 @@ pub struct WatchOptions {
     base_dir: Option<BaseDirectory>,
     recursive: bool,
     delay_ms: Option<u64>,
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    kinds: Option<Vec<WatchEventKindFilter>>,
+    /// Send [`WatcherEvent`]s, including errors and rescan notices, instead of plain events.
+    #[serde(default)]
+    report_errors: bool,
 }
 
 #[tauri::command]
@@ -86,7 +243,7 @@ pub async fn watch<R: Runtime>(
     webview: Webview<R>,
     paths: Vec<SafeFilePath>,
     options: WatchOptions,
-    on_event: Channel<Event>,
+    on_event: Channel<JsonValue>,
     global_scope: GlobalScope<Entry>,
     command_scope: CommandScope<Entry>,
 ) -> CommandResult<ResourceId> {
@@ -101,6 +258,18 @@ pub async fn watch<R: Runtime>(
         )?);
     }
 
+    let sender = EventSender {
+        filter: EventFilter {
+            scope: resolve_scope(&webview, &global_scope, &command_scope)?,
+            roots: resolved_paths.clone(),
+            include: parse_patterns(&options.include)?,
+            exclude: parse_patterns(&options.exclude)?,
+            kinds: options.kinds.clone(),
+        },
+        on_event,
+        report_errors: options.report_errors,
+    };
+
     let recursive_mode = if options.recursive {
         RecursiveMode::Recursive
     } else {
@@ -114,7 +283,7 @@ pub async fn watch<R: Runtime>(
             debouncer.watcher().watch(path.as_ref(), recursive_mode)?;
             debouncer.cache().add_root(path, recursive_mode);
         }
-        watch_debounced(on_event, rx);
+        watch_debounced(rx, sender);
         WatcherKind::Debouncer(debouncer)
     } else {
         let (tx, rx) = channel();
@@ -122,7 +291,7 @@ pub async fn watch<R: Runtime>(
         for path in &resolved_paths {
             watcher.watch(path.as_ref(), recursive_mode)?;
         }
-        watch_raw(on_event, rx);
+        watch_raw(rx, sender);
         WatcherKind::Watcher(watcher)
     };
 
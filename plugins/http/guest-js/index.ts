@@ -26,7 +26,7 @@
  * @module
  */
 
-import { invoke } from '@tauri-apps/api/core'
+import { invoke, Resource } from '@tauri-apps/api/core'
 
 /**
  * Configuration of a proxy that a Client should pass requests to.
@@ -86,6 +86,96 @@ export interface ClientOptions {
   proxy?: Proxy
 }
 
+/**
+ * Defaults of a {@linkcode Client}, applied to every request made with it.
+ *
+ * @since 2.1.0
+ */
+export interface ClientDefaults {
+  /** URL that relative request URLs are resolved against. */
+  baseUrl?: string | URL
+  /** Headers sent with every request, unless the request sets the same header. */
+  headers?: HeadersInit
+  /** Timeout of the whole request in milliseconds. */
+  timeout?: number
+}
+
+/**
+ * A reusable client created with {@linkcode createClient}.
+ *
+ * Requests made with it share connections with the other requests that use
+ * the same {@linkcode ClientOptions}.
+ *
+ * @since 2.1.0
+ */
+export class Client extends Resource {
+  /** URL that relative request URLs are resolved against. */
+  readonly baseUrl?: string
+
+  constructor(rid: number, baseUrl?: string) {
+    super(rid)
+    this.baseUrl = baseUrl
+  }
+
+  /**
+   * Fetches a resource with the defaults of this client, see {@linkcode fetch}.
+   *
+   * @example
+   * ```typescript
+   * const client = await createClient({ baseUrl: 'https://api.example.com/v1/' });
+   * const response = await client.fetch('users');
+   * ```
+   *
+   * @since 2.1.0
+   */
+  async fetch(
+    input: URL | Request | string,
+    init?: RequestInit
+  ): Promise<Response> {
+    return await fetch(input, { ...init, client: this })
+  }
+}
+
+/**
+ * Creates a reusable client with its own defaults. Closing it frees the client.
+ *
+ * @example
+ * ```typescript
+ * const client = await createClient({
+ *   baseUrl: 'https://api.example.com/v1/',
+ *   headers: { Authorization: `Bearer ${token}` },
+ *   timeout: 10000
+ * });
+ * const response = await client.fetch('users');
+ * await client.close();
+ * ```
+ *
+ * @since 2.1.0
+ */
+export async function createClient(
+  options?: ClientOptions & ClientDefaults
+): Promise<Client> {
+  const {
+    maxRedirections,
+    connectTimeout,
+    proxy,
+    baseUrl,
+    headers,
+    timeout
+  } = options ?? {}
+
+  const rid = await invoke<number>('plugin:http|create_client', {
+    options: { maxRedirections, connectTimeout, proxy },
+    defaults: {
+      baseUrl: baseUrl?.toString(),
+      headers: headers ? Array.from(new Headers(headers).entries()) : [],
+      timeout
+    }
+  })
+
+  return new Client(rid, baseUrl?.toString())
+}
+
 const ERROR_REQUEST_CANCELLED = 'Request canceled'
 
 /**
@@ -104,7 +194,7 @@ const ERROR_REQUEST_CANCELLED = 'Request canceled'
  */
 export async function fetch(
   input: URL | Request | string,
-  init?: RequestInit & ClientOptions
+  init?: RequestInit & ClientOptions & { client?: Client }
 ): Promise<Response> {
   // abort early here if needed
   const signal = init?.signal
@@ -115,12 +205,18 @@ export async function fetch(
   const maxRedirections = init?.maxRedirections
   const connectTimeout = init?.connectTimeout
   const proxy = init?.proxy
+  const client = init?.client
 
   // Remove these fields before creating the request
   if (init) {
     delete init.maxRedirections
     delete init.connectTimeout
     delete init.proxy
+    delete init.client
+  }
+
+  if (client?.baseUrl && typeof input === 'string') {
+    input = new URL(input, client.baseUrl)
   }
 
   const headers = init?.headers
@@ -172,7 +268,8 @@ export async function fetch(
       data,
       maxRedirections,
       connectTimeout,
-      proxy
+      proxy,
+      client: client?.rid
     }
   })
 
@@ -0,0 +1,266 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{str::FromStr, sync::Mutex, time::Duration};
+
+use http::{HeaderMap, HeaderName, HeaderValue, Method};
+use reqwest::{redirect::Policy, NoProxy};
+use serde::Deserialize;
+use url::Url;
+
+use crate::Result;
+
+/// Number of clients kept in the cache before the least recently used one is evicted.
+const MAX_CACHED_CLIENTS: usize = 16;
+
+/// Configuration of a [`reqwest::Client`], clients with the same configuration are shared.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientOptions {
+    /// Timeout for the connect phase in milliseconds.
+    pub connect_timeout: Option<u64>,
+    /// Maximum number of redirects to follow, `0` disables redirects.
+    pub max_redirections: Option<usize>,
+    pub proxy: Option<Proxy>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Proxy {
+    pub all: Option<UrlOrConfig>,
+    pub http: Option<UrlOrConfig>,
+    pub https: Option<UrlOrConfig>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[serde(untagged)]
+pub enum UrlOrConfig {
+    Url(String),
+    Config(ProxyConfig),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProxyConfig {
+    pub url: String,
+    pub basic_auth: Option<BasicAuth>,
+    pub no_proxy: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
+pub struct BasicAuth {
+    pub username: String,
+    pub password: String,
+}
+
+#[inline]
+fn proxy_creator(
+    url_or_config: UrlOrConfig,
+    proxy_fn: fn(String) -> reqwest::Result<reqwest::Proxy>,
+) -> reqwest::Result<reqwest::Proxy> {
+    match url_or_config {
+        UrlOrConfig::Url(url) => Ok(proxy_fn(url)?),
+        UrlOrConfig::Config(ProxyConfig {
+            url,
+            basic_auth,
+            no_proxy,
+        }) => {
+            let mut proxy = proxy_fn(url)?;
+            if let Some(basic_auth) = basic_auth {
+                proxy = proxy.basic_auth(&basic_auth.username, &basic_auth.password);
+            }
+            if let Some(no_proxy) = no_proxy {
+                proxy = proxy.no_proxy(NoProxy::from_string(&no_proxy));
+            }
+            Ok(proxy)
+        }
+    }
+}
+
+fn attach_proxy(
+    proxy: Proxy,
+    mut builder: reqwest::ClientBuilder,
+) -> crate::Result<reqwest::ClientBuilder> {
+    let Proxy { all, http, https } = proxy;
+
+    if let Some(all) = all {
+        let proxy = proxy_creator(all, reqwest::Proxy::all)?;
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(http) = http {
+        let proxy = proxy_creator(http, reqwest::Proxy::http)?;
+        builder = builder.proxy(proxy);
+    }
+
+    if let Some(https) = https {
+        let proxy = proxy_creator(https, reqwest::Proxy::https)?;
+        builder = builder.proxy(proxy);
+    }
+
+    Ok(builder)
+}
+
+/// Clients keyed by their configuration, so requests reuse pooled connections and TLS sessions.
+///
+/// Ordered from the least to the most recently used client.
+#[derive(Default)]
+pub(crate) struct ClientCache(Mutex<Vec<(ClientOptions, reqwest::Client)>>);
+
+impl ClientCache {
+    pub(crate) fn get_or_build(
+        &self,
+        options: &ClientOptions,
+        build: impl FnOnce(reqwest::ClientBuilder) -> reqwest::ClientBuilder,
+    ) -> Result<reqwest::Client> {
+        let mut clients = self.0.lock().unwrap();
+        if let Some(i) = clients.iter().position(|(o, _)| o == options) {
+            let entry = clients.remove(i);
+            let client = entry.1.clone();
+            clients.push(entry);
+            return Ok(client);
+        }
+
+        let mut builder = reqwest::ClientBuilder::new();
+
+        if let Some(timeout) = options.connect_timeout {
+            builder = builder.connect_timeout(Duration::from_millis(timeout));
+        }
+
+        if let Some(max_redirections) = options.max_redirections {
+            builder = builder.redirect(if max_redirections == 0 {
+                Policy::none()
+            } else {
+                Policy::limited(max_redirections)
+            });
+        }
+
+        if let Some(proxy_config) = options.proxy.clone() {
+            builder = attach_proxy(proxy_config, builder)?;
+        }
+
+        let client = build(builder).build()?;
+
+        if clients.len() >= MAX_CACHED_CLIENTS {
+            // requests in flight keep their client alive
+            clients.remove(0);
+        }
+        clients.push((options.clone(), client.clone()));
+
+        Ok(client)
+    }
+}
+
+/// Defaults applied to every request made with an [`HttpClient`].
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientDefaults {
+    /// URL that relative request URLs are resolved against.
+    pub base_url: Option<Url>,
+    /// Headers sent with every request, unless the request sets the same header.
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    /// Timeout of the whole request in milliseconds.
+    pub timeout: Option<u64>,
+}
+
+/// A client with its own defaults, stored in the resources table of a webview.
+pub struct HttpClient {
+    client: reqwest::Client,
+    base_url: Option<Url>,
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+}
+
+impl tauri::Resource for HttpClient {}
+
+impl HttpClient {
+    pub(crate) fn new(client: reqwest::Client, defaults: ClientDefaults) -> Result<Self> {
+        let mut headers = HeaderMap::new();
+        for (h, v) in defaults.headers {
+            headers.append(HeaderName::from_str(&h)?, HeaderValue::from_str(&v)?);
+        }
+
+        Ok(Self {
+            client,
+            base_url: defaults.base_url,
+            headers,
+            timeout: defaults.timeout.map(Duration::from_millis),
+        })
+    }
+
+    pub fn base_url(&self) -> Option<&Url> {
+        self.base_url.as_ref()
+    }
+
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// Starts a request to `url`, resolved against the base URL, with the default headers and timeout.
+    pub fn request(&self, method: Method, url: &str) -> Result<reqwest::RequestBuilder> {
+        let url = match &self.base_url {
+            Some(base_url) => base_url.join(url)?,
+            None => Url::parse(url)?,
+        };
+
+        let mut request = self
+            .client
+            .request(method, url)
+            .headers(self.headers.clone());
+        if let Some(timeout) = self.timeout {
+            request = request.timeout(timeout);
+        }
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Gets the client for `options` from `cache`, returning whether it had to be built.
+    fn built(cache: &ClientCache, options: &ClientOptions) -> bool {
+        let mut built = false;
+        cache
+            .get_or_build(options, |builder| {
+                built = true;
+                builder
+            })
+            .unwrap();
+        built
+    }
+
+    fn timeout(ms: u64) -> ClientOptions {
+        ClientOptions {
+            connect_timeout: Some(ms),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn same_options_reuse_client() {
+        let cache = ClientCache::default();
+        assert!(built(&cache, &ClientOptions::default()));
+        assert!(!built(&cache, &ClientOptions::default()));
+        assert!(built(&cache, &timeout(10)));
+        assert!(!built(&cache, &timeout(10)));
+        assert!(!built(&cache, &ClientOptions::default()));
+    }
+
+    #[test]
+    fn least_recently_used_client_is_evicted() {
+        let cache = ClientCache::default();
+        for ms in 0..MAX_CACHED_CLIENTS as u64 {
+            assert!(built(&cache, &timeout(ms)));
+        }
+        // the first client becomes the most recently used one
+        assert!(!built(&cache, &timeout(0)));
+
+        assert!(built(&cache, &timeout(100)));
+        assert!(!built(&cache, &timeout(0)));
+        assert!(built(&cache, &timeout(1)));
+    }
+}
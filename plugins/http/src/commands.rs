@@ -2,10 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use std::{future::Future, pin::Pin, str::FromStr, sync::Arc, time::Duration};
+use std::{future::Future, pin::Pin, str::FromStr, sync::Arc};
 
 use http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
-use reqwest::{redirect::Policy, NoProxy};
 use serde::{Deserialize, Serialize};
 use tauri::{
     async_runtime::Mutex,
@@ -16,6 +15,7 @@ use tauri::{
 use tokio::sync::oneshot::{channel, Receiver, Sender};
 
 use crate::{
+    client::{ClientDefaults, ClientOptions, HttpClient},
     scope::{Entry, Scope},
     Error, Http, Result,
 };
@@ -82,87 +82,56 @@ pub struct ClientConfig {
     url: url::Url,
     headers: Vec<(String, String)>,
     data: Option<Vec<u8>>,
-    connect_timeout: Option<u64>,
-    max_redirections: Option<usize>,
-    proxy: Option<Proxy>,
+    #[serde(flatten)]
+    options: ClientOptions,
+    /// Resource id of a client created with [`create_client`], used instead of the shared clients.
+    client: Option<ResourceId>,
 }
 
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct Proxy {
-    all: Option<UrlOrConfig>,
-    http: Option<UrlOrConfig>,
-    https: Option<UrlOrConfig>,
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-#[serde(untagged)]
-pub enum UrlOrConfig {
-    Url(String),
-    Config(ProxyConfig),
-}
-
-#[derive(Debug, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct ProxyConfig {
-    url: String,
-    basic_auth: Option<BasicAuth>,
-    no_proxy: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-pub struct BasicAuth {
-    username: String,
-    password: String,
-}
-
-#[inline]
-fn proxy_creator(
-    url_or_config: UrlOrConfig,
-    proxy_fn: fn(String) -> reqwest::Result<reqwest::Proxy>,
-) -> reqwest::Result<reqwest::Proxy> {
-    match url_or_config {
-        UrlOrConfig::Url(url) => Ok(proxy_fn(url)?),
-        UrlOrConfig::Config(ProxyConfig {
-            url,
-            basic_auth,
-            no_proxy,
-        }) => {
-            let mut proxy = proxy_fn(url)?;
-            if let Some(basic_auth) = basic_auth {
-                proxy = proxy.basic_auth(&basic_auth.username, &basic_auth.password);
-            }
-            if let Some(no_proxy) = no_proxy {
-                proxy = proxy.no_proxy(NoProxy::from_string(&no_proxy));
-            }
-            Ok(proxy)
+impl ClientConfig {
+    /// Fails if client options are set on a request made with a created client,
+    /// since the configuration of a client is fixed when it is created.
+    fn check_client_options(&self) -> crate::Result<()> {
+        if self.client.is_some() && self.options != ClientOptions::default() {
+            return Err(Error::ClientOptionsWithClient);
         }
+        Ok(())
     }
 }
 
-fn attach_proxy(
-    proxy: Proxy,
-    mut builder: reqwest::ClientBuilder,
-) -> crate::Result<reqwest::ClientBuilder> {
-    let Proxy { all, http, https } = proxy;
-
-    if let Some(all) = all {
-        let proxy = proxy_creator(all, reqwest::Proxy::all)?;
-        builder = builder.proxy(proxy);
-    }
-
-    if let Some(http) = http {
-        let proxy = proxy_creator(http, reqwest::Proxy::http)?;
-        builder = builder.proxy(proxy);
-    }
+/// Parses the headers sent by the webview, skipping the forbidden ones.
+fn parse_headers(headers_raw: Vec<(String, String)>) -> crate::Result<HeaderMap> {
+    let mut headers = HeaderMap::new();
+    for (h, v) in headers_raw {
+        let name = HeaderName::from_str(&h)?;
+        #[cfg(not(feature = "unsafe-headers"))]
+        if is_unsafe_header(&name) {
+            continue;
+        }
 
-    if let Some(https) = https {
-        let proxy = proxy_creator(https, reqwest::Proxy::https)?;
-        builder = builder.proxy(proxy);
+        headers.append(name, HeaderValue::from_str(&v)?);
     }
+    Ok(headers)
+}
 
-    Ok(builder)
+/// Creates a client with its own defaults, resolving to its resource id.
+///
+/// The underlying connection pool is shared with the other clients that have the same `options`.
+#[command]
+pub async fn create_client<R: Runtime>(
+    webview: Webview<R>,
+    state: State<'_, Http>,
+    options: Option<ClientOptions>,
+    defaults: Option<ClientDefaults>,
+) -> crate::Result<ResourceId> {
+    #[cfg_attr(feature = "unsafe-headers", allow(unused_mut))]
+    let mut defaults = defaults.unwrap_or_default();
+    #[cfg(not(feature = "unsafe-headers"))]
+    defaults
+        .headers
+        .retain(|(h, _)| HeaderName::from_str(h).map_or(true, |name| !is_unsafe_header(&name)));
+
+    state.create_client(&webview, options.unwrap_or_default(), defaults)
 }
 
 #[command]
@@ -173,29 +142,19 @@ pub async fn fetch<R: Runtime>(
     command_scope: CommandScope<Entry>,
     global_scope: GlobalScope<Entry>,
 ) -> crate::Result<ResourceId> {
+    client_config.check_client_options()?;
     let ClientConfig {
         method,
         url,
         headers: headers_raw,
         data,
-        connect_timeout,
-        max_redirections,
-        proxy,
+        options,
+        client,
     } = client_config;
 
     let scheme = url.scheme();
     let method = Method::from_bytes(method.as_bytes())?;
-
-    let mut headers = HeaderMap::new();
-    for (h, v) in headers_raw {
-        let name = HeaderName::from_str(&h)?;
-        #[cfg(not(feature = "unsafe-headers"))]
-        if is_unsafe_header(&name) {
-            continue;
-        }
-
-        headers.append(name, HeaderValue::from_str(&v)?);
-    }
+    let mut headers = parse_headers(headers_raw)?;
 
     match scheme {
         "http" | "https" => {
@@ -213,30 +172,21 @@ pub async fn fetch<R: Runtime>(
             )
             .is_allowed(&url)
             {
-                let mut builder = reqwest::ClientBuilder::new();
-
-                if let Some(timeout) = connect_timeout {
-                    builder = builder.connect_timeout(Duration::from_millis(timeout));
-                }
-
-                if let Some(max_redirections) = max_redirections {
-                    builder = builder.redirect(if max_redirections == 0 {
-                        Policy::none()
-                    } else {
-                        Policy::limited(max_redirections)
-                    });
-                }
-
-                if let Some(proxy_config) = proxy {
-                    builder = attach_proxy(proxy_config, builder)?;
-                }
-
-                #[cfg(feature = "cookies")]
-                {
-                    builder = builder.cookie_provider(state.cookies_jar.clone());
-                }
-
-                let mut request = builder.build()?.request(method.clone(), url);
+                let mut request = match client {
+                    Some(rid) => {
+                        let client = webview.resources_table().get::<HttpClient>(rid)?;
+                        // merged now so the defaults below do not override the ones of the client
+                        for name in client.headers().keys() {
+                            if !headers.contains_key(name) {
+                                for value in client.headers().get_all(name) {
+                                    headers.append(name.clone(), value.clone());
+                                }
+                            }
+                        }
+                        client.request(method.clone(), url.as_str())?
+                    }
+                    None => state.client(&options)?.request(method.clone(), url),
+                };
 
                 // POST and PUT requests should always have a 0 length content-length,
                 // if there is no body. https://fetch.spec.whatwg.org/#http-network-or-cache-fetch
@@ -410,3 +360,43 @@ fn is_unsafe_header(header: &HeaderName) -> bool {
         lower.starts_with("proxy-") || lower.starts_with("sec-")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(json: serde_json::Value) -> ClientConfig {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn client_options_with_client_are_rejected() {
+        let with_options = config(serde_json::json!({
+            "method": "GET",
+            "url": "http://localhost:8080",
+            "headers": [],
+            "client": 1,
+            "connectTimeout": 10
+        }));
+        assert!(matches!(
+            with_options.check_client_options(),
+            Err(Error::ClientOptionsWithClient)
+        ));
+
+        let without_client = config(serde_json::json!({
+            "method": "GET",
+            "url": "http://localhost:8080",
+            "headers": [],
+            "connectTimeout": 10
+        }));
+        assert!(without_client.check_client_options().is_ok());
+
+        let without_options = config(serde_json::json!({
+            "method": "GET",
+            "url": "http://localhost:8080",
+            "headers": [],
+            "client": 1
+        }));
+        assert!(without_options.check_client_options().is_ok());
+    }
+}
@@ -31,6 +31,9 @@ pub enum Error {
     SchemeNotSupport(String),
     #[error("Request canceled")]
     RequestCanceled,
+    /// Client options were set on a request made with a client created by `create_client`.
+    #[error("client options cannot be set on a request made with an existing client")]
+    ClientOptionsWithClient,
     #[error(transparent)]
     FsError(#[from] tauri_plugin_fs::Error),
     #[error("failed to process data url")]
@@ -9,18 +9,60 @@
 pub use reqwest;
 use tauri::{
     plugin::{Builder, TauriPlugin},
-    Manager, Runtime,
+    Manager, ResourceId, Runtime, Webview,
 };
 
+pub use client::{
+    BasicAuth, ClientDefaults, ClientOptions, HttpClient, Proxy, ProxyConfig, UrlOrConfig,
+};
 pub use error::{Error, Result};
 
+mod client;
 mod commands;
 mod error;
 mod scope;
 
-pub(crate) struct Http {
+/// Access to the HTTP clients of the plugin.
+pub struct Http {
     #[cfg(feature = "cookies")]
     cookies_jar: std::sync::Arc<reqwest::cookie::Jar>,
+    clients: client::ClientCache,
+}
+
+impl Http {
+    /// Returns the shared client for `options`, building it on first use.
+    ///
+    /// Clients are kept in a cache so requests with the same options reuse connections.
+    pub fn client(&self, options: &ClientOptions) -> Result<reqwest::Client> {
+        self.clients.get_or_build(options, |builder| {
+            #[cfg(feature = "cookies")]
+            let builder = builder.cookie_provider(self.cookies_jar.clone());
+            builder
+        })
+    }
+
+    /// Creates a client with its own defaults in the resources table of `webview`,
+    /// so the webview can pass its resource id to `fetch`.
+    pub fn create_client<R: Runtime>(
+        &self,
+        webview: &Webview<R>,
+        options: ClientOptions,
+        defaults: ClientDefaults,
+    ) -> Result<ResourceId> {
+        let client = HttpClient::new(self.client(&options)?, defaults)?;
+        Ok(webview.resources_table().add(client))
+    }
+}
+
+/// Extensions to [`tauri::App`], [`tauri::AppHandle`], [`tauri::WebviewWindow`], [`tauri::Webview`] and [`tauri::Window`] to access the HTTP APIs.
+pub trait HttpExt<R: Runtime> {
+    fn http(&self) -> &Http;
+}
+
+impl<R: Runtime, T: Manager<R>> HttpExt<R> for T {
+    fn http(&self) -> &Http {
+        self.state::<Http>().inner()
+    }
 }
 
 pub fn init<R: Runtime>() -> TauriPlugin<R> {
@@ -29,6 +71,7 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             let state = Http {
                 #[cfg(feature = "cookies")]
                 cookies_jar: std::sync::Arc::new(reqwest::cookie::Jar::default()),
+                clients: Default::default(),
             };
 
             app.manage(state);
@@ -40,6 +83,7 @@ pub fn init<R: Runtime>() -> TauriPlugin<R> {
             commands::fetch_cancel,
             commands::fetch_send,
             commands::fetch_read_body,
+            commands::create_client,
         ])
         .build()
 }
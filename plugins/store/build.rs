@@ -17,6 +17,9 @@ const COMMANDS: &[&str] = &[
     "length",
     "reload",
     "save",
+    "batch",
+    "watch",
+    "unwatch",
 ];
 
 fn main() {
@@ -32,7 +32,7 @@ fn main() {
                     store.set(
                         "appSettings",
                         json!({ "theme": theme, "launchAtLogin": launch_at_login }),
-                    );
+                    )?;
                 }
                 Err(err) => {
                     eprintln!("Error loading settings: {err}");
@@ -4,7 +4,7 @@
 
 import { listen, type UnlistenFn } from '@tauri-apps/api/event'
 
-import { invoke, Resource } from '@tauri-apps/api/core'
+import { Channel, invoke, Resource } from '@tauri-apps/api/core'
 
 interface ChangePayload<T> {
   path: string
@@ -14,6 +14,50 @@ interface ChangePayload<T> {
   exists: boolean
 }
 
+interface BatchChangePayload<T> {
+  path: string
+  resourceId?: number
+  changes: Array<{ key: string; value: T; exists: boolean }>
+}
+
+/**
+ * A change of a single key delivered to {@linkcode Store.watch} subscribers.
+ */
+export interface StoreChange<T> {
+  key: string
+  value?: T
+  exists: boolean
+}
+
+/**
+ * Selects the keys a {@linkcode Store.watch} subscriber is notified about.
+ * If neither `key` nor `prefix` is set, every key is watched.
+ */
+export interface WatchOptions {
+  /** Watch a single key. */
+  key?: string
+  /** Watch every key starting with this prefix. */
+  prefix?: string
+}
+
+/**
+ * An operation applied by {@linkcode Store.batch}.
+ */
+export type BatchOperation =
+  | { op: 'set'; key: string; value: unknown; ttl?: number }
+  | { op: 'delete'; key: string }
+
+/**
+ * Options for {@linkcode Store.set}.
+ */
+export interface SetOptions {
+  /**
+   * Time to live in milliseconds. Once it elapsed the key is removed from the store,
+   * emitting a change event with `exists: false`.
+   */
+  ttl?: number
+}
+
 /**
  * Options to create a store
  */
@@ -105,8 +149,8 @@ export class LazyStore implements IStore {
     await this.store
   }
 
-  async set(key: string, value: unknown): Promise<void> {
-    return (await this.store).set(key, value)
+  async set(key: string, value: unknown, options?: SetOptions): Promise<void> {
+    return (await this.store).set(key, value, options)
   }
 
   async get<T>(key: string): Promise<T | undefined> {
@@ -153,6 +197,17 @@ export class LazyStore implements IStore {
     await (await this.store).save()
   }
 
+  async batch(operations: BatchOperation[]): Promise<void> {
+    await (await this.store).batch(operations)
+  }
+
+  async watch<T>(
+    cb: (change: StoreChange<T>) => void,
+    options?: WatchOptions
+  ): Promise<UnlistenFn> {
+    return (await this.store).watch<T>(cb, options)
+  }
+
   async onKeyChange<T>(
     key: string,
     cb: (value: T | undefined) => void
@@ -226,11 +281,12 @@ export class Store extends Resource implements IStore {
     )
   }
 
-  async set(key: string, value: unknown): Promise<void> {
+  async set(key: string, value: unknown, options?: SetOptions): Promise<void> {
     await invoke('plugin:store|set', {
       rid: this.rid,
       key,
-      value
+      value,
+      ttl: options?.ttl
     })
   }
 
@@ -288,13 +344,33 @@ export class Store extends Resource implements IStore {
     await invoke('plugin:store|save', { rid: this.rid })
   }
 
+  async batch(operations: BatchOperation[]): Promise<void> {
+    await invoke('plugin:store|batch', { rid: this.rid, operations })
+  }
+
+  async watch<T>(
+    cb: (change: StoreChange<T>) => void,
+    options?: WatchOptions
+  ): Promise<UnlistenFn> {
+    const onChange = new Channel<StoreChange<T>>()
+    onChange.onmessage = cb
+    const id = await invoke<number>('plugin:store|watch', {
+      rid: this.rid,
+      ...options,
+      onChange
+    })
+    return () => {
+      void invoke('plugin:store|unwatch', { rid: this.rid, id })
+    }
+  }
+
   async onKeyChange<T>(
     key: string,
     cb: (value: T | undefined) => void
   ): Promise<UnlistenFn> {
-    return await listen<ChangePayload<T>>('store://change', (event) => {
-      if (event.payload.resourceId === this.rid && event.payload.key === key) {
-        cb(event.payload.exists ? event.payload.value : undefined)
+    return await this.onChange<T>((changedKey, value) => {
+      if (changedKey === key) {
+        cb(value)
       }
     })
   }
@@ -302,14 +378,31 @@ export class Store extends Resource implements IStore {
   async onChange<T>(
     cb: (key: string, value: T | undefined) => void
   ): Promise<UnlistenFn> {
-    return await listen<ChangePayload<T>>('store://change', (event) => {
-      if (event.payload.resourceId === this.rid) {
-        cb(
-          event.payload.key,
-          event.payload.exists ? event.payload.value : undefined
-        )
+    const unlistenChange = await listen<ChangePayload<T>>(
+      'store://change',
+      (event) => {
+        if (event.payload.resourceId === this.rid) {
+          cb(
+            event.payload.key,
+            event.payload.exists ? event.payload.value : undefined
+          )
+        }
       }
-    })
+    )
+    const unlistenBatchChange = await listen<BatchChangePayload<T>>(
+      'store://batch-change',
+      (event) => {
+        if (event.payload.resourceId === this.rid) {
+          for (const change of event.payload.changes) {
+            cb(change.key, change.exists ? change.value : undefined)
+          }
+        }
+      }
+    )
+    return () => {
+      unlistenChange()
+      unlistenBatchChange()
+    }
   }
 }
 
@@ -317,15 +410,22 @@ interface IStore {
   /**
    * Inserts a key-value pair into the store.
    *
+   * If the key starts with `/` it is treated as a JSON Pointer to a nested value, e.g. `/editor/font/size`.
+   *
+   * Pass `options.ttl` to remove the key after the given number of milliseconds.
+   *
    * @param key
    * @param value
+   * @param options
    * @returns
    */
-  set(key: string, value: unknown): Promise<void>
+  set(key: string, value: unknown, options?: SetOptions): Promise<void>
 
   /**
    * Returns the value for the given `key` or `undefined` if the key does not exist.
    *
+   * If the key starts with `/` it is treated as a JSON Pointer to a nested value, e.g. `/editor/font/size`.
+   *
    * @param key
    * @returns
    */
@@ -407,6 +507,30 @@ interface IStore {
    */
   save(): Promise<void>
 
+  /**
+   * Applies several set and delete operations at once.
+   *
+   * Either all operations are applied or none of them,
+   * and listeners are notified once after all of them were applied.
+   *
+   * @param operations
+   * @returns
+   */
+  batch(operations: BatchOperation[]): Promise<void>
+
+  /**
+   * Subscribe to changes of the keys selected by `options`.
+   *
+   * Unlike {@linkcode onChange}, only the matching changes are sent to this window.
+   * @param cb
+   * @param options
+   * @returns A promise resolving to a function to stop watching.
+   */
+  watch<T>(
+    cb: (change: StoreChange<T>) => void,
+    options?: WatchOptions
+  ): Promise<UnlistenFn>
+
   /**
    * Listen to changes on a store key.
    * @param key
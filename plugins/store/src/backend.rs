@@ -0,0 +1,234 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// Where a [`Store`](crate::Store) is persisted.
+///
+/// The store content is persisted as one or more chunks, each one being the output of the store's
+/// serialize function. Backends persisting the whole store use a single chunk, while
+/// [per key](Self::per_key) backends store a chunk for every key so a save only writes the keys that changed.
+pub trait StoreBackend: Send + Sync {
+    /// Reads every persisted chunk.
+    fn load(&self) -> crate::Result<Vec<Vec<u8>>>;
+
+    /// Reads a backup of the persisted chunks, used when [`Self::load`] fails.
+    fn load_backup(&self) -> crate::Result<Option<Vec<Vec<u8>>>> {
+        Ok(None)
+    }
+
+    /// Replaces the persisted store with a single chunk holding every key.
+    fn save(&self, bytes: &[u8]) -> crate::Result<()>;
+
+    /// Whether the store should persist its changes with [`Self::save_keys`] instead of [`Self::save`].
+    fn per_key(&self) -> bool {
+        false
+    }
+
+    /// Persists the chunks of the given keys, a `None` chunk removes the key.
+    fn save_keys(&self, chunks: Vec<(String, Option<Vec<u8>>)>) -> crate::Result<()> {
+        let _ = chunks;
+        Err(std::io::Error::from(std::io::ErrorKind::Unsupported).into())
+    }
+
+    /// The file backing the store, watched by `StoreBuilder::watch_file`.
+    fn path(&self) -> Option<&Path> {
+        None
+    }
+}
+
+/// Returns a path next to `path` with `suffix` appended to its file name,
+/// e.g. `settings.json` -> `settings.json.bak`.
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut file_name = path.file_name().map(ToOwned::to_owned).unwrap_or_default();
+    file_name.push(suffix);
+    path.with_file_name(file_name)
+}
+
+/// Writes `bytes` to a temporary file next to `path`, flushes it to disk
+/// and atomically renames it over `path`.
+///
+/// If `backup` is set, the previous content of `path` is kept as a `.bak` sibling.
+fn write_atomic(path: &Path, bytes: &[u8], backup: bool) -> std::io::Result<()> {
+    let tmp_path = sibling_path(path, ".tmp");
+
+    let write = || -> std::io::Result<()> {
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(bytes)?;
+        file.sync_all()
+    };
+    if let Err(e) = write() {
+        let _ = fs::remove_file(&tmp_path);
+        return Err(e);
+    }
+
+    if backup && path.exists() {
+        fs::rename(path, sibling_path(path, ".bak"))?;
+    }
+    fs::rename(&tmp_path, path)?;
+
+    // make sure the rename itself is persisted
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        let _ = File::open(parent).and_then(|dir| dir.sync_all());
+    }
+
+    Ok(())
+}
+
+/// Persists the store in a single file. This is the default backend.
+///
+/// Saves are atomic: the content is written to a temporary file first and then renamed over the
+/// store file, so a crash in the middle of a save never leaves a truncated store behind.
+#[derive(Debug, Clone)]
+pub struct FileBackend {
+    path: PathBuf,
+    backup: bool,
+}
+
+impl FileBackend {
+    /// Creates a backend persisting the store at `path`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            backup: false,
+        }
+    }
+
+    /// Keep the previous file content as a `.bak` file on every save.
+    pub fn backup(mut self, backup: bool) -> Self {
+        self.backup = backup;
+        self
+    }
+}
+
+impl StoreBackend for FileBackend {
+    fn load(&self) -> crate::Result<Vec<Vec<u8>>> {
+        Ok(vec![fs::read(&self.path)?])
+    }
+
+    fn load_backup(&self) -> crate::Result<Option<Vec<Vec<u8>>>> {
+        let backup_path = sibling_path(&self.path, ".bak");
+        if !self.backup || !backup_path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(vec![fs::read(backup_path)?]))
+    }
+
+    fn save(&self, bytes: &[u8]) -> crate::Result<()> {
+        fs::create_dir_all(self.path.parent().expect("invalid store path"))?;
+        write_atomic(&self.path, bytes, self.backup)?;
+        Ok(())
+    }
+
+    fn path(&self) -> Option<&Path> {
+        Some(&self.path)
+    }
+}
+
+/// Keeps the store in memory only, useful for tests.
+///
+/// Clones share the same data, so a clone can be kept to inspect what the store saved.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryBackend(Arc<Mutex<Option<Vec<u8>>>>);
+
+impl MemoryBackend {
+    /// Creates an empty backend.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a backend holding the given serialized store.
+    pub fn with_data(bytes: Vec<u8>) -> Self {
+        Self(Arc::new(Mutex::new(Some(bytes))))
+    }
+
+    /// The last saved serialized store.
+    pub fn data(&self) -> Option<Vec<u8>> {
+        self.0.lock().unwrap().clone()
+    }
+}
+
+impl StoreBackend for MemoryBackend {
+    fn load(&self) -> crate::Result<Vec<Vec<u8>>> {
+        Ok(self.0.lock().unwrap().iter().cloned().collect())
+    }
+
+    fn save(&self, bytes: &[u8]) -> crate::Result<()> {
+        self.0.lock().unwrap().replace(bytes.to_vec());
+        Ok(())
+    }
+}
+
+/// Persists every key in its own row of a SQLite database,
+/// so saving a large store only writes the keys that changed.
+#[cfg(feature = "sqlite")]
+pub struct SqliteBackend {
+    connection: Mutex<rusqlite::Connection>,
+}
+
+#[cfg(feature = "sqlite")]
+impl SqliteBackend {
+    /// Opens or creates the database at `path`.
+    pub fn open(path: impl AsRef<Path>) -> crate::Result<Self> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let connection = rusqlite::Connection::open(path)?;
+        connection.execute(
+            "CREATE TABLE IF NOT EXISTS store (key TEXT PRIMARY KEY NOT NULL, value BLOB NOT NULL)",
+            (),
+        )?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl StoreBackend for SqliteBackend {
+    fn load(&self) -> crate::Result<Vec<Vec<u8>>> {
+        let connection = self.connection.lock().unwrap();
+        let mut statement = connection.prepare("SELECT value FROM store")?;
+        let chunks = statement
+            .query_map((), |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        Ok(chunks)
+    }
+
+    fn save(&self, bytes: &[u8]) -> crate::Result<()> {
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        transaction.execute("DELETE FROM store", ())?;
+        transaction.execute("INSERT INTO store (key, value) VALUES ('', ?1)", (bytes,))?;
+        transaction.commit()?;
+        Ok(())
+    }
+
+    fn per_key(&self) -> bool {
+        true
+    }
+
+    fn save_keys(&self, chunks: Vec<(String, Option<Vec<u8>>)>) -> crate::Result<()> {
+        let mut connection = self.connection.lock().unwrap();
+        let transaction = connection.transaction()?;
+        for (key, chunk) in chunks {
+            match chunk {
+                Some(chunk) => transaction.execute(
+                    "INSERT OR REPLACE INTO store (key, value) VALUES (?1, ?2)",
+                    (&key, &chunk),
+                )?,
+                None => transaction.execute("DELETE FROM store WHERE key = ?1", (&key,))?,
+            };
+        }
+        transaction.commit()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,125 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{DeserializeFn, SerializeFn};
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    XChaCha20Poly1305, XNonce,
+};
+use std::sync::Arc;
+
+/// Marks an encrypted store file and the format version.
+const MAGIC: &[u8] = b"TSE\x01";
+const NONCE_LEN: usize = 24;
+
+/// Wraps the given (de)serialize functions so the serialized bytes are encrypted with `key`.
+pub(crate) fn encrypted(
+    key: &[u8; 32],
+    serialize_fn: SerializeFn,
+    deserialize_fn: DeserializeFn,
+) -> (SerializeFn, DeserializeFn) {
+    let cipher = Arc::new(XChaCha20Poly1305::new(key.into()));
+
+    let encrypt_cipher = cipher.clone();
+    let serialize: SerializeFn = Arc::new(move |cache| {
+        let bytes = serialize_fn(cache)?;
+        Ok(encrypt(&encrypt_cipher, &bytes)?)
+    });
+    let deserialize: DeserializeFn = Arc::new(move |bytes| {
+        let bytes = decrypt(&cipher, bytes)?;
+        deserialize_fn(&bytes)
+    });
+
+    (serialize, deserialize)
+}
+
+fn encrypt(cipher: &XChaCha20Poly1305, plaintext: &[u8]) -> crate::Result<Vec<u8>> {
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext)
+        .map_err(|_| crate::Error::Encryption)?;
+
+    let mut bytes = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    bytes.extend_from_slice(MAGIC);
+    bytes.extend_from_slice(&nonce);
+    bytes.extend_from_slice(&ciphertext);
+    Ok(bytes)
+}
+
+fn decrypt(cipher: &XChaCha20Poly1305, bytes: &[u8]) -> crate::Result<Vec<u8>> {
+    let data = bytes
+        .strip_prefix(MAGIC)
+        .filter(|data| data.len() >= NONCE_LEN)
+        .ok_or(crate::Error::Decryption(
+            "the file is not an encrypted store",
+        ))?;
+    let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+    cipher
+        .decrypt(XNonce::from_slice(nonce), ciphertext)
+        .map_err(|_| crate::Error::Decryption("wrong key or tampered data"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value as JsonValue};
+    use std::collections::HashMap;
+
+    fn functions(key: &[u8; 32]) -> (SerializeFn, DeserializeFn) {
+        encrypted(
+            key,
+            Arc::new(crate::default_serialize),
+            Arc::new(crate::default_deserialize),
+        )
+    }
+
+    fn cache() -> HashMap<String, JsonValue> {
+        HashMap::from([
+            ("token".to_owned(), json!("secret")),
+            ("count".to_owned(), json!(1)),
+        ])
+    }
+
+    fn decryption_error(error: Box<dyn std::error::Error + Send + Sync>) -> &'static str {
+        match error.downcast_ref::<crate::Error>() {
+            Some(crate::Error::Decryption(reason)) => reason,
+            _ => panic!("expected a decryption error, got {error}"),
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        let (serialize, deserialize) = functions(&[7; 32]);
+        let bytes = serialize(&cache()).unwrap();
+
+        assert!(bytes.starts_with(MAGIC));
+        assert!(!bytes.windows(6).any(|w| w == b"secret"));
+        assert_eq!(deserialize(&bytes).unwrap(), cache());
+        // every save uses a new nonce
+        assert_ne!(serialize(&cache()).unwrap(), bytes);
+    }
+
+    #[test]
+    fn wrong_key_or_tampered_data_fails() {
+        let (serialize, _) = functions(&[7; 32]);
+        let (_, deserialize) = functions(&[8; 32]);
+        let mut bytes = serialize(&cache()).unwrap();
+
+        assert_eq!(
+            decryption_error(deserialize(&bytes).unwrap_err()),
+            "wrong key or tampered data"
+        );
+
+        let (_, deserialize) = functions(&[7; 32]);
+        *bytes.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            decryption_error(deserialize(&bytes).unwrap_err()),
+            "wrong key or tampered data"
+        );
+        assert_eq!(
+            decryption_error(deserialize(br#"{"token":"secret"}"#).unwrap_err()),
+            "the file is not an encrypted store"
+        );
+    }
+}
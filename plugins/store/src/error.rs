@@ -29,6 +29,35 @@ pub enum Error {
     /// Deserialize function not found
     #[error("Deserialize Function \"{0}\" not found")]
     DeserializeFunctionNotFound(String),
+    /// The schema given to the store builder is not a valid JSON Schema.
+    #[error("Invalid store schema: {0}")]
+    InvalidSchema(String),
+    /// The store content does not match its schema.
+    #[error("Store validation failed: {0}")]
+    Validation(String),
+    /// A store migration failed.
+    #[error("Failed to run store migration {0}. {1}")]
+    Migration(u64, Box<dyn std::error::Error + Send + Sync>),
+    /// The persisted store version is newer than the latest migration, e.g. after a downgrade of the app.
+    #[error("Store version {0} is newer than the latest migration version {1}")]
+    NewerVersion(u64, u64),
+    /// Failed to encrypt the store.
+    #[error("Failed to encrypt store")]
+    Encryption,
+    /// Failed to decrypt the store.
+    #[error("Failed to decrypt store. {0}")]
+    Decryption(&'static str),
+    /// Watcher error.
+    #[cfg(feature = "watch")]
+    #[error(transparent)]
+    Watch(#[from] notify::Error),
+    /// The key is a JSON Pointer that cannot be resolved.
+    #[error("Invalid key path \"{0}\"")]
+    InvalidKeyPath(String),
+    /// SQLite backend error.
+    #[cfg(feature = "sqlite")]
+    #[error(transparent)]
+    Sqlite(#[from] rusqlite::Error),
     /// Some Tauri API failed
     #[error(transparent)]
     Tauri(#[from] tauri::Error),
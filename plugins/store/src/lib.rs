@@ -11,7 +11,11 @@
     html_favicon_url = "https://github.com/tauri-apps/tauri/raw/dev/app-icon.png"
 )]
 
+#[cfg(feature = "sqlite")]
+pub use backend::SqliteBackend;
+pub use backend::{FileBackend, MemoryBackend, StoreBackend};
 pub use error::{Error, Result};
+pub use listener::{KeyPattern, StoreChange};
 use serde::{Deserialize, Serialize};
 pub use serde_json::Value as JsonValue;
 use std::{
@@ -20,14 +24,27 @@ use std::{
     sync::{Arc, Mutex},
     time::Duration,
 };
-pub use store::{resolve_store_path, DeserializeFn, SerializeFn, Store, StoreBuilder};
+pub use store::{
+    resolve_store_path, DeserializeFn, DeserializeResult, Migration, SerializeFn, SerializeResult,
+    Store, StoreBuilder, Transaction, EXPIRY_KEY, VERSION_KEY,
+};
+#[cfg(feature = "watch")]
+pub use watcher::ConflictPolicy;
+
 use tauri::{
+    ipc::Channel,
     plugin::{self, TauriPlugin},
-    AppHandle, Manager, ResourceId, RunEvent, Runtime, State,
+    AppHandle, Manager, ResourceId, RunEvent, Runtime, State, Webview, WindowEvent,
 };
 
+mod backend;
+mod encryption;
 mod error;
+mod listener;
+mod pointer;
 mod store;
+#[cfg(feature = "watch")]
+mod watcher;
 
 #[derive(Serialize, Clone)]
 #[serde(rename_all = "camelCase")]
@@ -39,7 +56,21 @@ struct ChangePayload<'a> {
     exists: bool,
 }
 
-#[derive(Debug)]
+#[derive(Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+struct BatchChangePayload<'a> {
+    path: &'a Path,
+    resource_id: Option<u32>,
+    changes: Vec<KeyChange<'a>>,
+}
+
+#[derive(Serialize, Clone)]
+struct KeyChange<'a> {
+    key: &'a str,
+    value: Option<&'a JsonValue>,
+    exists: bool,
+}
+
 struct StoreState {
     stores: Arc<Mutex<HashMap<PathBuf, ResourceId>>>,
     serialize_fns: HashMap<String, SerializeFn>,
@@ -48,6 +79,19 @@ struct StoreState {
     default_deserialize: DeserializeFn,
 }
 
+#[derive(Deserialize)]
+#[serde(tag = "op", rename_all = "camelCase")]
+enum BatchOperation {
+    Set {
+        key: String,
+        value: JsonValue,
+        ttl: Option<u64>,
+    },
+    Delete {
+        key: String,
+    },
+}
+
 #[derive(Serialize, Deserialize)]
 #[serde(untagged)]
 enum AutoSave {
@@ -82,7 +126,8 @@ fn builder<R: Runtime>(
             .serialize_fns
             .get(&serialize_fn_name)
             .ok_or_else(|| crate::Error::SerializeFunctionNotFound(serialize_fn_name))?;
-        builder = builder.serialize(*serialize_fn);
+        let serialize_fn = serialize_fn.clone();
+        builder = builder.serialize(move |cache| serialize_fn(cache));
     }
 
     if let Some(deserialize_fn_name) = deserialize_fn_name {
@@ -90,7 +135,8 @@ fn builder<R: Runtime>(
             .deserialize_fns
             .get(&deserialize_fn_name)
             .ok_or_else(|| crate::Error::DeserializeFunctionNotFound(deserialize_fn_name))?;
-        builder = builder.deserialize(*deserialize_fn);
+        let deserialize_fn = deserialize_fn.clone();
+        builder = builder.deserialize(move |bytes| deserialize_fn(bytes));
     }
 
     if create_new {
@@ -139,10 +185,13 @@ async fn set<R: Runtime>(
     rid: ResourceId,
     key: String,
     value: JsonValue,
+    ttl: Option<u64>,
 ) -> Result<()> {
     let store = app.resources_table().get::<Store<R>>(rid)?;
-    store.set(key, value);
-    Ok(())
+    match ttl {
+        Some(ttl) => store.set_with_ttl(key, value, Duration::from_millis(ttl)),
+        None => store.set(key, value),
+    }
 }
 
 #[tauri::command]
@@ -166,21 +215,19 @@ async fn has<R: Runtime>(app: AppHandle<R>, rid: ResourceId, key: String) -> Res
 #[tauri::command]
 async fn delete<R: Runtime>(app: AppHandle<R>, rid: ResourceId, key: String) -> Result<bool> {
     let store = app.resources_table().get::<Store<R>>(rid)?;
-    Ok(store.delete(key))
+    store.delete(key)
 }
 
 #[tauri::command]
 async fn clear<R: Runtime>(app: AppHandle<R>, rid: ResourceId) -> Result<()> {
     let store = app.resources_table().get::<Store<R>>(rid)?;
-    store.clear();
-    Ok(())
+    store.clear()
 }
 
 #[tauri::command]
 async fn reset<R: Runtime>(app: AppHandle<R>, rid: ResourceId) -> Result<()> {
     let store = app.resources_table().get::<Store<R>>(rid)?;
-    store.reset();
-    Ok(())
+    store.reset()
 }
 
 #[tauri::command]
@@ -222,6 +269,53 @@ async fn save<R: Runtime>(app: AppHandle<R>, rid: ResourceId) -> Result<()> {
     store.save()
 }
 
+#[tauri::command]
+async fn batch<R: Runtime>(
+    app: AppHandle<R>,
+    rid: ResourceId,
+    operations: Vec<BatchOperation>,
+) -> Result<()> {
+    let store = app.resources_table().get::<Store<R>>(rid)?;
+    store.transaction(|tx| {
+        for operation in operations {
+            match operation {
+                BatchOperation::Set { key, value, ttl } => match ttl {
+                    Some(ttl) => tx.set_with_ttl(key, value, Duration::from_millis(ttl))?,
+                    None => tx.set(key, value)?,
+                },
+                BatchOperation::Delete { key } => {
+                    tx.delete(key);
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+#[tauri::command]
+async fn watch<R: Runtime>(
+    app: AppHandle<R>,
+    webview: Webview<R>,
+    rid: ResourceId,
+    key: Option<String>,
+    prefix: Option<String>,
+    on_change: Channel<StoreChange>,
+) -> Result<u32> {
+    let store = app.resources_table().get::<Store<R>>(rid)?;
+    let pattern = match (key, prefix) {
+        (Some(key), _) => KeyPattern::Key(key),
+        (None, Some(prefix)) => KeyPattern::Prefix(prefix),
+        (None, None) => KeyPattern::Any,
+    };
+    Ok(store.watch_webview(webview.label(), pattern, on_change))
+}
+
+#[tauri::command]
+async fn unwatch<R: Runtime>(app: AppHandle<R>, rid: ResourceId, id: u32) -> Result<bool> {
+    let store = app.resources_table().get::<Store<R>>(rid)?;
+    Ok(store.unlisten(id))
+}
+
 pub trait StoreExt<R: Runtime> {
     /// Create a store or load an existing store with default settings at the given path.
     ///
@@ -306,15 +400,11 @@ impl<R: Runtime, T: Manager<R>> StoreExt<R> for T {
     }
 }
 
-fn default_serialize(
-    cache: &HashMap<String, JsonValue>,
-) -> std::result::Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>> {
+fn default_serialize(cache: &HashMap<String, JsonValue>) -> SerializeResult {
     Ok(serde_json::to_vec_pretty(&cache)?)
 }
 
-fn default_deserialize(
-    bytes: &[u8],
-) -> std::result::Result<HashMap<String, JsonValue>, Box<dyn std::error::Error + Send + Sync>> {
+fn default_deserialize(bytes: &[u8]) -> DeserializeResult {
     serde_json::from_slice(bytes).map_err(Into::into)
 }
 
@@ -330,8 +420,8 @@ impl Default for Builder {
         Self {
             serialize_fns: Default::default(),
             deserialize_fns: Default::default(),
-            default_serialize,
-            default_deserialize,
+            default_serialize: Arc::new(default_serialize),
+            default_deserialize: Arc::new(default_deserialize),
         }
     }
 }
@@ -359,14 +449,20 @@ impl Builder {
     ///             .build(),
     ///     );
     /// ```
-    pub fn register_serialize_fn(mut self, name: String, serialize_fn: SerializeFn) -> Self {
-        self.serialize_fns.insert(name, serialize_fn);
+    pub fn register_serialize_fn<F>(mut self, name: String, serialize_fn: F) -> Self
+    where
+        F: Fn(&HashMap<String, JsonValue>) -> SerializeResult + Send + Sync + 'static,
+    {
+        self.serialize_fns.insert(name, Arc::new(serialize_fn));
         self
     }
 
     /// Register a deserialize function to access it from the JavaScript side
-    pub fn register_deserialize_fn(mut self, name: String, deserialize_fn: DeserializeFn) -> Self {
-        self.deserialize_fns.insert(name, deserialize_fn);
+    pub fn register_deserialize_fn<F>(mut self, name: String, deserialize_fn: F) -> Self
+    where
+        F: Fn(&[u8]) -> DeserializeResult + Send + Sync + 'static,
+    {
+        self.deserialize_fns.insert(name, Arc::new(deserialize_fn));
         self
     }
 
@@ -388,14 +484,20 @@ impl Builder {
     ///             .build(),
     ///     );
     /// ```
-    pub fn default_serialize_fn(mut self, serialize_fn: SerializeFn) -> Self {
-        self.default_serialize = serialize_fn;
+    pub fn default_serialize_fn<F>(mut self, serialize_fn: F) -> Self
+    where
+        F: Fn(&HashMap<String, JsonValue>) -> SerializeResult + Send + Sync + 'static,
+    {
+        self.default_serialize = Arc::new(serialize_fn);
         self
     }
 
     /// Use this deserialize function for stores by default
-    pub fn default_deserialize_fn(mut self, deserialize_fn: DeserializeFn) -> Self {
-        self.default_deserialize = deserialize_fn;
+    pub fn default_deserialize_fn<F>(mut self, deserialize_fn: F) -> Self
+    where
+        F: Fn(&[u8]) -> DeserializeResult + Send + Sync + 'static,
+    {
+        self.default_deserialize = Arc::new(deserialize_fn);
         self
     }
 
@@ -415,7 +517,7 @@ impl Builder {
         plugin::Builder::new("store")
             .invoke_handler(tauri::generate_handler![
                 load, get_store, set, get, has, delete, clear, reset, keys, values, length,
-                entries, reload, save,
+                entries, reload, save, batch, watch, unwatch,
             ])
             .setup(move |app_handle, _api| {
                 app_handle.manage(StoreState {
@@ -427,8 +529,30 @@ impl Builder {
                 });
                 Ok(())
             })
-            .on_event(|app_handle, event| {
-                if let RunEvent::Exit = event {
+            .on_event(|app_handle, event| match event {
+                RunEvent::WindowEvent {
+                    label,
+                    event: WindowEvent::Destroyed,
+                    ..
+                } => {
+                    // the webview of a webview window shares its label
+                    let collection = app_handle.state::<StoreState>();
+                    // stores lock `stores` while they emit a change, so it must not be held
+                    // while locking a store
+                    let rids: Vec<ResourceId> = collection
+                        .stores
+                        .lock()
+                        .unwrap()
+                        .values()
+                        .copied()
+                        .collect();
+                    for rid in rids {
+                        if let Ok(store) = app_handle.resources_table().get::<Store<R>>(rid) {
+                            store.unlisten_webview(label);
+                        }
+                    }
+                }
+                RunEvent::Exit => {
                     let collection = app_handle.state::<StoreState>();
                     let stores = collection.stores.lock().unwrap();
                     for (path, rid) in stores.iter() {
@@ -439,6 +563,7 @@ impl Builder {
                         }
                     }
                 }
+                _ => {}
             })
             .build()
     }
@@ -0,0 +1,201 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use serde::Serialize;
+use serde_json::Value as JsonValue;
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Arc, Mutex,
+};
+use tauri::ipc::Channel;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+
+static NEXT_LISTENER_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Selects the keys a change listener is notified about.
+///
+/// Converting a string into a pattern treats `*` as any key
+/// and a trailing `*` as a key prefix, e.g. `"editor.*"`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyPattern {
+    /// Every key.
+    Any,
+    /// A single key.
+    Key(String),
+    /// Every key starting with the given prefix.
+    Prefix(String),
+}
+
+impl KeyPattern {
+    /// Returns `true` if `key` matches this pattern.
+    pub fn matches(&self, key: &str) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Key(k) => k == key,
+            Self::Prefix(prefix) => key.starts_with(prefix.as_str()),
+        }
+    }
+}
+
+impl From<&str> for KeyPattern {
+    fn from(pattern: &str) -> Self {
+        if pattern == "*" {
+            Self::Any
+        } else if let Some(prefix) = pattern.strip_suffix('*') {
+            Self::Prefix(prefix.into())
+        } else {
+            Self::Key(pattern.into())
+        }
+    }
+}
+
+impl From<String> for KeyPattern {
+    fn from(pattern: String) -> Self {
+        pattern.as_str().into()
+    }
+}
+
+/// A change of a single store key.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StoreChange {
+    pub key: String,
+    pub value: Option<JsonValue>,
+    pub exists: bool,
+}
+
+enum Sink {
+    Channel(Channel<StoreChange>),
+    Callback(UnboundedSender<StoreChange>),
+}
+
+struct Listener {
+    id: u32,
+    pattern: KeyPattern,
+    sink: Sink,
+    /// Label of the webview that registered the listener.
+    webview: Option<String>,
+}
+
+/// Change listeners registered on a store.
+#[derive(Clone, Default)]
+pub(crate) struct Listeners(Arc<Mutex<Vec<Listener>>>);
+
+impl Listeners {
+    fn add(&self, pattern: KeyPattern, sink: Sink, webview: Option<String>) -> u32 {
+        let id = NEXT_LISTENER_ID.fetch_add(1, Ordering::Relaxed);
+        self.0.lock().unwrap().push(Listener {
+            id,
+            pattern,
+            sink,
+            webview,
+        });
+        id
+    }
+
+    /// Sends the changes matching `pattern` to the given channel.
+    ///
+    /// If the channel belongs to a webview, the listener is removed with [`Self::remove_webview`].
+    pub fn add_channel(
+        &self,
+        pattern: KeyPattern,
+        channel: Channel<StoreChange>,
+        webview: Option<String>,
+    ) -> u32 {
+        self.add(pattern, Sink::Channel(channel), webview)
+    }
+
+    /// Calls `f` with the changes matching `pattern`.
+    ///
+    /// The callback runs on the async runtime, so it can freely access the store.
+    pub fn add_callback<F>(&self, pattern: KeyPattern, f: F) -> u32
+    where
+        F: Fn(&str, Option<&JsonValue>) + Send + Sync + 'static,
+    {
+        let (tx, mut rx) = unbounded_channel::<StoreChange>();
+        tauri::async_runtime::spawn(async move {
+            while let Some(change) = rx.recv().await {
+                f(&change.key, change.value.as_ref());
+            }
+        });
+        self.add(pattern, Sink::Callback(tx), None)
+    }
+
+    /// Removes the listener with the given id.
+    pub fn remove(&self, id: u32) -> bool {
+        let mut listeners = self.0.lock().unwrap();
+        let len = listeners.len();
+        listeners.retain(|l| l.id != id);
+        listeners.len() != len
+    }
+
+    /// Removes the listeners registered by the webview with the given label.
+    pub fn remove_webview(&self, label: &str) {
+        self.0
+            .lock()
+            .unwrap()
+            .retain(|l| l.webview.as_deref() != Some(label));
+    }
+
+    /// Notifies the matching listeners, dropping the ones that are gone.
+    pub fn notify(&self, key: &str, value: Option<&JsonValue>) {
+        let mut listeners = self.0.lock().unwrap();
+        if listeners.is_empty() {
+            return;
+        }
+        let change = StoreChange {
+            key: key.into(),
+            value: value.cloned(),
+            exists: value.is_some(),
+        };
+        listeners.retain(|listener| {
+            if !listener.pattern.matches(key) {
+                return true;
+            }
+            match &listener.sink {
+                Sink::Channel(channel) => channel.send(change.clone()).is_ok(),
+                Sink::Callback(sender) => sender.send(change.clone()).is_ok(),
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A channel counting the changes sent to it.
+    fn channel() -> (Channel<StoreChange>, Arc<AtomicU32>) {
+        let count = Arc::new(AtomicU32::new(0));
+        let channel = Channel::new({
+            let count = count.clone();
+            move |_| {
+                count.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+        });
+        (channel, count)
+    }
+
+    #[test]
+    fn webview_listeners_are_removed() {
+        let listeners = Listeners::default();
+        let (main, main_count) = channel();
+        let (other, other_count) = channel();
+        let (app, app_count) = channel();
+        listeners.add_channel(KeyPattern::Any, main, Some("main".into()));
+        listeners.add_channel("editor.*".into(), other, Some("other".into()));
+        listeners.add_channel(KeyPattern::Any, app, None);
+
+        listeners.notify("editor.font", None);
+        listeners.remove_webview("main");
+        listeners.notify("editor.font", None);
+        listeners.remove_webview("other");
+        listeners.notify("editor.font", None);
+
+        assert_eq!(main_count.load(Ordering::Relaxed), 1);
+        assert_eq!(other_count.load(Ordering::Relaxed), 2);
+        assert_eq!(app_count.load(Ordering::Relaxed), 3);
+    }
+}
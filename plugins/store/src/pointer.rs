@@ -0,0 +1,207 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+//! [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901) addressing of store values.
+//!
+//! A key starting with `/` is a pointer whose first reference token is the top-level store key,
+//! e.g. `/editor/font/size`.
+
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+/// Splits `key` into its reference tokens, or returns `None` if it is not a JSON Pointer.
+pub(crate) fn parse(key: &str) -> Option<Vec<String>> {
+    key.strip_prefix('/')
+        .map(|pointer| pointer.split('/').map(unescape).collect())
+}
+
+fn unescape(token: &str) -> String {
+    token.replace("~1", "/").replace("~0", "~")
+}
+
+fn invalid(tokens: &[String]) -> crate::Error {
+    let pointer = tokens
+        .iter()
+        .map(|t| format!("/{}", t.replace('~', "~0").replace('/', "~1")))
+        .collect();
+    crate::Error::InvalidKeyPath(pointer)
+}
+
+/// Parses an array index token. `-` refers to the element after the last one.
+fn index(token: &str, len: usize) -> Option<usize> {
+    if token == "-" {
+        Some(len)
+    } else if token.len() > 1 && token.starts_with('0') {
+        None
+    } else {
+        token.parse().ok()
+    }
+}
+
+pub(crate) fn get<'a>(
+    cache: &'a HashMap<String, JsonValue>,
+    tokens: &[String],
+) -> Option<&'a JsonValue> {
+    let (first, rest) = tokens.split_first()?;
+    let mut value = cache.get(first)?;
+    for token in rest {
+        value = match value {
+            JsonValue::Object(map) => map.get(token)?,
+            JsonValue::Array(array) => array.get(index(token, array.len())?)?,
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+fn get_mut<'a>(mut value: &'a mut JsonValue, tokens: &[String]) -> Option<&'a mut JsonValue> {
+    for token in tokens {
+        value = match value {
+            JsonValue::Object(map) => map.get_mut(token)?,
+            JsonValue::Array(array) => {
+                let index = index(token, array.len())?;
+                array.get_mut(index)?
+            }
+            _ => return None,
+        };
+    }
+    Some(value)
+}
+
+/// Sets the value at the pointer, creating missing parent objects.
+pub(crate) fn set(
+    cache: &mut HashMap<String, JsonValue>,
+    tokens: &[String],
+    value: JsonValue,
+) -> crate::Result<()> {
+    let Some((first, rest)) = tokens.split_first() else {
+        return Err(invalid(tokens));
+    };
+    let Some((last, parents)) = rest.split_last() else {
+        cache.insert(first.clone(), value);
+        return Ok(());
+    };
+
+    let mut target = cache
+        .entry(first.clone())
+        .or_insert_with(|| JsonValue::Object(Map::new()));
+    for token in parents {
+        target = match target {
+            JsonValue::Object(map) => map
+                .entry(token.clone())
+                .or_insert_with(|| JsonValue::Object(Map::new())),
+            JsonValue::Array(array) => {
+                let index = index(token, array.len()).ok_or_else(|| invalid(tokens))?;
+                array.get_mut(index).ok_or_else(|| invalid(tokens))?
+            }
+            _ => return Err(invalid(tokens)),
+        };
+    }
+
+    match target {
+        JsonValue::Object(map) => {
+            map.insert(last.clone(), value);
+        }
+        JsonValue::Array(array) => match index(last, array.len()) {
+            Some(index) if index < array.len() => array[index] = value,
+            Some(index) if index == array.len() => array.push(value),
+            _ => return Err(invalid(tokens)),
+        },
+        _ => return Err(invalid(tokens)),
+    }
+    Ok(())
+}
+
+/// Removes the value at the pointer, returning it if it existed.
+pub(crate) fn remove(
+    cache: &mut HashMap<String, JsonValue>,
+    tokens: &[String],
+) -> Option<JsonValue> {
+    let (first, rest) = tokens.split_first()?;
+    let Some((last, parents)) = rest.split_last() else {
+        return cache.remove(first);
+    };
+
+    match get_mut(cache.get_mut(first)?, parents)? {
+        JsonValue::Object(map) => map.remove(last),
+        JsonValue::Array(array) => {
+            let index = index(last, array.len()).filter(|i| *i < array.len())?;
+            Some(array.remove(index))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn cache() -> HashMap<String, JsonValue> {
+        let mut cache = HashMap::new();
+        cache.insert(
+            "editor".to_string(),
+            json!({ "font": { "size": 12 }, "rulers": [80, 100] }),
+        );
+        cache.insert("a/b".to_string(), json!(true));
+        cache
+    }
+
+    #[test]
+    fn parse_pointer() {
+        assert_eq!(parse("editor"), None);
+        assert_eq!(
+            parse("/editor/font/size"),
+            Some(vec!["editor".into(), "font".into(), "size".into()])
+        );
+        assert_eq!(parse("/a~1b"), Some(vec!["a/b".into()]));
+        assert_eq!(parse("/~01"), Some(vec!["~1".into()]));
+    }
+
+    #[test]
+    fn get_nested() {
+        let cache = cache();
+        let get = |key| get(&cache, &parse(key).unwrap()).cloned();
+        assert_eq!(get("/editor/font/size"), Some(json!(12)));
+        assert_eq!(get("/editor/rulers/1"), Some(json!(100)));
+        assert_eq!(get("/a~1b"), Some(json!(true)));
+        assert_eq!(get("/editor/rulers/01"), None);
+        assert_eq!(get("/editor/missing"), None);
+    }
+
+    #[test]
+    fn set_nested() {
+        let mut cache = cache();
+        set(&mut cache, &parse("/editor/font/size").unwrap(), json!(14)).unwrap();
+        set(&mut cache, &parse("/editor/rulers/-").unwrap(), json!(120)).unwrap();
+        set(
+            &mut cache,
+            &parse("/window/size/width").unwrap(),
+            json!(800),
+        )
+        .unwrap();
+        assert_eq!(
+            cache["editor"],
+            json!({ "font": { "size": 14 }, "rulers": [80, 100, 120] })
+        );
+        assert_eq!(cache["window"], json!({ "size": { "width": 800 } }));
+        assert!(set(&mut cache, &parse("/editor/font/size/x").unwrap(), json!(1)).is_err());
+        assert!(set(&mut cache, &parse("/editor/rulers/5").unwrap(), json!(1)).is_err());
+    }
+
+    #[test]
+    fn remove_nested() {
+        let mut cache = cache();
+        assert_eq!(
+            remove(&mut cache, &parse("/editor/rulers/0").unwrap()),
+            Some(json!(80))
+        );
+        assert_eq!(
+            remove(&mut cache, &parse("/editor/font").unwrap()),
+            Some(json!({ "size": 12 }))
+        );
+        assert_eq!(remove(&mut cache, &parse("/editor/font").unwrap()), None);
+        assert_eq!(cache["editor"], json!({ "rulers": [100] }));
+    }
+}
@@ -2,26 +2,105 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::{ChangePayload, StoreState};
+use crate::{
+    backend::{FileBackend, StoreBackend},
+    listener::{KeyPattern, Listeners, StoreChange},
+    BatchChangePayload, ChangePayload, KeyChange, StoreState,
+};
+use jsonschema::Validator;
+use schemars::JsonSchema;
+use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value as JsonValue;
 use std::{
-    collections::HashMap,
-    fs,
+    collections::{HashMap, HashSet},
     path::{Path, PathBuf},
     sync::{Arc, Mutex},
-    time::Duration,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tauri::{
+    ipc::Channel, path::BaseDirectory, AppHandle, Emitter, Manager, Resource, ResourceId, Runtime,
 };
-use tauri::{path::BaseDirectory, AppHandle, Emitter, Manager, Resource, ResourceId, Runtime};
 use tokio::{
     select,
-    sync::mpsc::{unbounded_channel, UnboundedSender},
+    sync::{
+        mpsc::{unbounded_channel, UnboundedSender},
+        Notify,
+    },
     time::sleep,
 };
 
-pub type SerializeFn =
-    fn(&HashMap<String, JsonValue>) -> Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>;
-pub type DeserializeFn =
-    fn(&[u8]) -> Result<HashMap<String, JsonValue>, Box<dyn std::error::Error + Send + Sync>>;
+pub type SerializeResult = Result<Vec<u8>, Box<dyn std::error::Error + Send + Sync>>;
+pub type DeserializeResult =
+    Result<HashMap<String, JsonValue>, Box<dyn std::error::Error + Send + Sync>>;
+
+pub type SerializeFn = Arc<dyn Fn(&HashMap<String, JsonValue>) -> SerializeResult + Send + Sync>;
+pub type DeserializeFn = Arc<dyn Fn(&[u8]) -> DeserializeResult + Send + Sync>;
+
+/// Converts an error returned by a [`SerializeFn`] or [`DeserializeFn`] into a [`crate::Error`],
+/// keeping errors that already are a [`crate::Error`] as they are.
+fn codec_error(
+    error: Box<dyn std::error::Error + Send + Sync>,
+    wrap: fn(Box<dyn std::error::Error + Send + Sync>) -> crate::Error,
+) -> crate::Error {
+    match error.downcast::<crate::Error>() {
+        Ok(error) => *error,
+        Err(error) => wrap(error),
+    }
+}
+
+/// Reserved key holding the store version when [`StoreBuilder::migrations`] are used.
+pub const VERSION_KEY: &str = "__version__";
+
+/// Reserved key holding the expiry times of the entries set with [`Store::set_with_ttl`].
+pub const EXPIRY_KEY: &str = "__expiry__";
+
+/// How long the expiry timer waits when no entry has a time to live.
+const IDLE_EXPIRY_CHECK: Duration = Duration::from_secs(60);
+
+type MigrationResult = Result<(), Box<dyn std::error::Error + Send + Sync>>;
+type MigrationFn = dyn Fn(&mut HashMap<String, JsonValue>) -> MigrationResult + Send + Sync;
+
+/// A step that migrates the store content to a new layout.
+///
+/// See [`StoreBuilder::migrations`].
+pub struct Migration {
+    version: u64,
+    description: &'static str,
+    migrate: Box<MigrationFn>,
+}
+
+impl Migration {
+    /// Creates a migration that brings the store to `version`.
+    pub fn new<F>(version: u64, description: &'static str, migrate: F) -> Self
+    where
+        F: Fn(&mut HashMap<String, JsonValue>) -> MigrationResult + Send + Sync + 'static,
+    {
+        Self {
+            version,
+            description,
+            migrate: Box::new(migrate),
+        }
+    }
+
+    /// The store version after this migration ran.
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Description of this migration.
+    pub fn description(&self) -> &'static str {
+        self.description
+    }
+}
+
+impl std::fmt::Debug for Migration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Migration")
+            .field("version", &self.version)
+            .field("description", &self.description)
+            .finish()
+    }
+}
 
 pub fn resolve_store_path<R: Runtime>(
     app: &AppHandle<R>,
@@ -30,6 +109,77 @@ pub fn resolve_store_path<R: Runtime>(
     Ok(dunce::simplified(&app.path().resolve(path, BaseDirectory::AppData)?).to_path_buf())
 }
 
+/// Milliseconds since the unix epoch.
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+/// Strips the expiry times from the persisted `data` and removes the entries that already expired.
+///
+/// Returns the expiry times of the remaining entries and the removed keys.
+fn take_expiry(data: &mut HashMap<String, JsonValue>) -> (HashMap<String, u64>, Vec<String>) {
+    let now = now_millis();
+    let mut expiry = HashMap::new();
+    let mut expired = Vec::new();
+    if let Some(JsonValue::Object(times)) = data.remove(EXPIRY_KEY) {
+        for (key, at) in times {
+            let Some(at) = at.as_u64() else {
+                continue;
+            };
+            if at <= now {
+                if data.remove(&key).is_some() {
+                    expired.push(key);
+                }
+            } else if data.contains_key(&key) {
+                expiry.insert(key, at);
+            }
+        }
+    }
+    (expiry, expired)
+}
+
+/// Periodically removes the expired entries of `store` until the store is dropped,
+/// triggering `auto_save` when entries were removed.
+fn spawn_expiry_timer<R: Runtime>(store: &Arc<Mutex<StoreInner<R>>>, auto_save: AutoSave) {
+    let notify = store.lock().unwrap().expiry_timer.clone();
+    let store = Arc::downgrade(store);
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let next_expiry = match store.upgrade() {
+                Some(store) => {
+                    let mut inner = store.lock().unwrap();
+                    let purged = inner.purge_expired();
+                    let next_expiry = inner.expiry.values().min().copied();
+                    drop(inner);
+                    if purged {
+                        let _ = auto_save.trigger(&store);
+                    }
+                    next_expiry
+                }
+                None => break,
+            };
+            let timeout = next_expiry
+                .map(|at| Duration::from_millis(at.saturating_sub(now_millis())))
+                .unwrap_or(IDLE_EXPIRY_CHECK);
+            select! {
+                _ = sleep(timeout) => (),
+                _ = notify.notified() => (),
+            }
+        }
+    });
+}
+
+#[cfg(feature = "watch")]
+fn hash_chunks(chunks: &[Vec<u8>]) -> u64 {
+    use std::hash::{Hash, Hasher};
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    chunks.hash(&mut hasher);
+    hasher.finish()
+}
+
 /// Builds a [`Store`]
 pub struct StoreBuilder<R: Runtime> {
     app: AppHandle<R>,
@@ -39,6 +189,15 @@ pub struct StoreBuilder<R: Runtime> {
     deserialize_fn: DeserializeFn,
     auto_save: Option<Duration>,
     create_new: bool,
+    backup: bool,
+    backend: Option<Arc<dyn StoreBackend>>,
+    schema: Option<crate::Result<JsonValue>>,
+    migrations: Vec<Migration>,
+    encryption_key: Option<[u8; 32]>,
+    #[cfg(feature = "watch")]
+    watch_file: bool,
+    #[cfg(feature = "watch")]
+    conflict_policy: crate::ConflictPolicy,
 }
 
 impl<R: Runtime> StoreBuilder<R> {
@@ -56,8 +215,8 @@ impl<R: Runtime> StoreBuilder<R> {
     pub fn new<M: Manager<R>, P: AsRef<Path>>(manager: &M, path: P) -> Self {
         let app = manager.app_handle().clone();
         let state = app.state::<StoreState>();
-        let serialize_fn = state.default_serialize;
-        let deserialize_fn = state.default_deserialize;
+        let serialize_fn = state.default_serialize.clone();
+        let deserialize_fn = state.default_deserialize.clone();
         Self {
             app,
             path: path.as_ref().to_path_buf(),
@@ -66,6 +225,15 @@ impl<R: Runtime> StoreBuilder<R> {
             deserialize_fn,
             auto_save: Some(Duration::from_millis(100)),
             create_new: false,
+            backup: false,
+            backend: None,
+            schema: None,
+            migrations: Vec::new(),
+            encryption_key: None,
+            #[cfg(feature = "watch")]
+            watch_file: false,
+            #[cfg(feature = "watch")]
+            conflict_policy: Default::default(),
         }
     }
 
@@ -125,8 +293,11 @@ impl<R: Runtime> StoreBuilder<R> {
     ///     Ok(())
     ///   });
     /// ```
-    pub fn serialize(mut self, serialize: SerializeFn) -> Self {
-        self.serialize_fn = serialize;
+    pub fn serialize<F>(mut self, serialize: F) -> Self
+    where
+        F: Fn(&HashMap<String, JsonValue>) -> SerializeResult + Send + Sync + 'static,
+    {
+        self.serialize_fn = Arc::new(serialize);
         self
     }
 
@@ -143,8 +314,35 @@ impl<R: Runtime> StoreBuilder<R> {
     ///     Ok(())
     ///   });
     /// ```
-    pub fn deserialize(mut self, deserialize: DeserializeFn) -> Self {
-        self.deserialize_fn = deserialize;
+    pub fn deserialize<F>(mut self, deserialize: F) -> Self
+    where
+        F: Fn(&[u8]) -> DeserializeResult + Send + Sync + 'static,
+    {
+        self.deserialize_fn = Arc::new(deserialize);
+        self
+    }
+
+    /// Encrypts the store on disk with XChaCha20-Poly1305 using the given 256-bit key.
+    ///
+    /// Every save uses a fresh random nonce stored alongside the ciphertext.
+    /// Loading fails with [`crate::Error::Decryption`] if the key is wrong or the file was tampered with.
+    ///
+    /// The encryption is applied on top of the configured (de)serialize functions.
+    ///
+    /// # Examples
+    /// ```
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let key = [0u8; 32]; // load this from a secure location, e.g. the OS keychain
+    ///     let store = tauri_plugin_store::StoreBuilder::new(app, "secrets.bin")
+    ///       .encryption_key(key)
+    ///       .build()?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn encryption_key(mut self, key: [u8; 32]) -> Self {
+        self.encryption_key = Some(key);
         self
     }
 
@@ -178,6 +376,165 @@ impl<R: Runtime> StoreBuilder<R> {
         self
     }
 
+    /// Keep the previous on-disk state as a `.bak` file next to the store on every save.
+    ///
+    /// If the store file cannot be read or deserialized on load, the backup is used instead.
+    /// Only used by the default [`FileBackend`].
+    ///
+    /// # Examples
+    /// ```
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = tauri_plugin_store::StoreBuilder::new(app, "store.json")
+    ///       .backup(true)
+    ///       .build()?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn backup(mut self, backup: bool) -> Self {
+        self.backup = backup;
+        self
+    }
+
+    /// Persist the store with the given backend instead of a file at the store path.
+    ///
+    /// The store path still identifies the store, e.g. for [`crate::StoreExt::get_store`].
+    ///
+    /// # Examples
+    /// ```
+    /// use tauri_plugin_store::MemoryBackend;
+    ///
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = tauri_plugin_store::StoreBuilder::new(app, "store.json")
+    ///       .backend(MemoryBackend::new())
+    ///       .build()?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn backend(mut self, backend: impl StoreBackend + 'static) -> Self {
+        self.backend = Some(Arc::new(backend));
+        self
+    }
+
+    /// Validates the store content against the given JSON Schema.
+    ///
+    /// The whole store is validated as a JSON object when it is built and on every change, load and [`Store::reload`].
+    /// A persisted store that does not match the schema fails to build and its file is left untouched.
+    ///
+    /// # Examples
+    /// ```
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = tauri_plugin_store::StoreBuilder::new(app, "store.json")
+    ///       .schema(serde_json::json!({
+    ///         "type": "object",
+    ///         "properties": { "volume": { "type": "number" } }
+    ///       }))
+    ///       .build()?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn schema(mut self, schema: JsonValue) -> Self {
+        self.schema = Some(Ok(schema));
+        self
+    }
+
+    /// Validates the store content against the JSON Schema derived from `T`.
+    ///
+    /// See [`Self::schema`].
+    ///
+    /// # Examples
+    /// ```
+    /// #[derive(schemars::JsonSchema)]
+    /// struct Settings {
+    ///   volume: f64,
+    /// }
+    ///
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = tauri_plugin_store::StoreBuilder::new(app, "store.json")
+    ///       .schema_for::<Settings>()
+    ///       .build()?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn schema_for<T: JsonSchema>(mut self) -> Self {
+        self.schema = Some(serde_json::to_value(schemars::schema_for!(T)).map_err(Into::into));
+        self
+    }
+
+    /// Migrations applied to the on-disk state when the store is loaded.
+    ///
+    /// The store version is persisted under the reserved [`VERSION_KEY`] and every migration
+    /// with a higher version runs in ascending order. The migrated store is saved immediately.
+    /// Stores created from scratch start at the latest migration version.
+    ///
+    /// A store with a version newer than the latest migration, e.g. after a downgrade of the app,
+    /// fails to build with [`crate::Error::NewerVersion`] and is left as it is.
+    /// If a migration fails, the store fails to build with [`crate::Error::Migration`] and is left
+    /// as it is too, the version is only bumped once every migration succeeded.
+    ///
+    /// # Examples
+    /// ```
+    /// use tauri_plugin_store::Migration;
+    ///
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = tauri_plugin_store::StoreBuilder::new(app, "store.json")
+    ///       .migrations(vec![Migration::new(1, "rename theme key", |cache| {
+    ///         if let Some(theme) = cache.remove("theme") {
+    ///           cache.insert("appearance".into(), serde_json::json!({ "theme": theme }));
+    ///         }
+    ///         Ok(())
+    ///       })])
+    ///       .build()?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn migrations(mut self, migrations: Vec<Migration>) -> Self {
+        self.migrations = migrations;
+        self.migrations.sort_by_key(|m| m.version);
+        self
+    }
+
+    /// Watch the store file and reload the store when it is modified by another process.
+    ///
+    /// A `store://change` event is emitted for every key changed by the reload.
+    /// If the store has unsaved changes when the file is modified, the [`crate::ConflictPolicy`]
+    /// set with [`Self::on_conflict`] decides which state is kept.
+    ///
+    /// # Examples
+    /// ```
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = tauri_plugin_store::StoreBuilder::new(app, "store.json")
+    ///       .watch_file(true)
+    ///       .build()?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    #[cfg(feature = "watch")]
+    pub fn watch_file(mut self, watch: bool) -> Self {
+        self.watch_file = watch;
+        self
+    }
+
+    /// How to resolve an external modification of the store file while the store has unsaved changes.
+    ///
+    /// Defaults to [`crate::ConflictPolicy::DiskWins`]. Only used with [`Self::watch_file`].
+    #[cfg(feature = "watch")]
+    pub fn on_conflict(mut self, policy: crate::ConflictPolicy) -> Self {
+        self.conflict_policy = policy;
+        self
+    }
+
     pub(crate) fn build_inner(mut self) -> crate::Result<(Arc<Store<R>>, ResourceId)> {
         let stores = self.app.state::<StoreState>().stores.clone();
         let mut stores = stores.lock().unwrap();
@@ -196,22 +553,89 @@ impl<R: Runtime> StoreBuilder<R> {
         //     return Err(crate::Error::AlreadyExists(self.path));
         // }
 
-        let mut store_inner = StoreInner::new(
-            self.app.clone(),
-            self.path.clone(),
-            self.defaults.take(),
-            self.serialize_fn,
-            self.deserialize_fn,
-        );
+        let validator = self
+            .schema
+            .take()
+            .map(|schema| {
+                jsonschema::validator_for(&schema?)
+                    .map(Arc::new)
+                    .map_err(|e| crate::Error::InvalidSchema(e.to_string()))
+            })
+            .transpose()?;
+
+        let (serialize_fn, deserialize_fn) = match self.encryption_key.take() {
+            Some(key) => crate::encryption::encrypted(&key, self.serialize_fn, self.deserialize_fn),
+            None => (self.serialize_fn, self.deserialize_fn),
+        };
+
+        let backend = self
+            .backend
+            .take()
+            .unwrap_or_else(|| Arc::new(FileBackend::new(&self.path).backup(self.backup)));
+
+        let migrations: Arc<[Migration]> = std::mem::take(&mut self.migrations).into();
+        let defaults = self.defaults.take();
+        let mut store_inner = StoreInner {
+            app: self.app.clone(),
+            path: self.path.clone(),
+            cache: defaults.clone().unwrap_or_default(),
+            defaults,
+            serialize_fn,
+            deserialize_fn,
+            backend,
+            validator,
+            version: migrations.last().map(|m| m.version),
+            migrations,
+            listeners: Listeners::default(),
+            changed: HashSet::new(),
+            expiry: HashMap::new(),
+            expiry_timer: Arc::new(Notify::new()),
+            #[cfg(feature = "watch")]
+            conflict_policy: self.conflict_policy,
+            #[cfg(feature = "watch")]
+            disk_hash: None,
+        };
 
         if !self.create_new {
-            let _ = store_inner.load();
+            // a store that cannot be loaded must not be overwritten by the next save,
+            // and nothing can observe the store yet so purged entries emit no change event
+            match store_inner.load_persisted() {
+                Ok(_) => {}
+                Err(crate::Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {}
+                Err(e) => return Err(e),
+            }
         }
+        // the defaults must match the schema too
+        store_inner.validate(&store_inner.cache)?;
+
+        #[cfg(feature = "watch")]
+        let backend_path = store_inner.backend.path().map(ToOwned::to_owned);
+        let store_inner = Arc::new(Mutex::new(store_inner));
+        let auto_save = AutoSave {
+            delay: self.auto_save,
+            debounce_sender: Arc::new(Mutex::new(None)),
+        };
+        spawn_expiry_timer(&store_inner, auto_save.clone());
+
+        #[cfg(feature = "watch")]
+        let watcher = match (self.watch_file, backend_path) {
+            (true, Some(path)) => Some(crate::watcher::watch(
+                &path,
+                store_inner.clone(),
+                auto_save.clone(),
+            )?),
+            (true, None) => {
+                log::warn!("the backend of store {:?} cannot be watched", self.path);
+                None
+            }
+            (false, _) => None,
+        };
 
         let store = Store {
-            auto_save: self.auto_save,
-            auto_save_debounce_sender: Arc::new(Mutex::new(None)),
-            store: Arc::new(Mutex::new(store_inner)),
+            auto_save,
+            store: store_inner,
+            #[cfg(feature = "watch")]
+            _watcher: watcher,
         };
 
         let store = Arc::new(store);
@@ -225,6 +649,9 @@ impl<R: Runtime> StoreBuilder<R> {
     ///
     /// If a store with the same path has already been loaded its instance is returned.
     ///
+    /// Fails if the persisted store exists but cannot be loaded, e.g. with the wrong encryption key,
+    /// so it is never overwritten by the defaults.
+    ///
     /// # Examples
     /// ```
     /// tauri::Builder::default()
@@ -240,144 +667,768 @@ impl<R: Runtime> StoreBuilder<R> {
     }
 }
 
-enum AutoSaveMessage {
-    Reset,
-    Cancel,
-}
-
-#[derive(Clone)]
-struct StoreInner<R: Runtime> {
-    app: AppHandle<R>,
-    path: PathBuf,
+/// A set of pending changes created by [`Store::transaction`].
+///
+/// Keys starting with `/` are treated as JSON Pointers, like in [`Store`].
+#[derive(Debug)]
+pub struct Transaction {
     cache: HashMap<String, JsonValue>,
-    defaults: Option<HashMap<String, JsonValue>>,
-    serialize_fn: SerializeFn,
-    deserialize_fn: DeserializeFn,
+    expiry: HashMap<String, u64>,
+    /// Top-level keys changed in this transaction.
+    changed: Vec<String>,
 }
 
-impl<R: Runtime> StoreInner<R> {
-    fn new(
-        app: AppHandle<R>,
-        path: PathBuf,
-        defaults: Option<HashMap<String, JsonValue>>,
-        serialize_fn: SerializeFn,
-        deserialize_fn: DeserializeFn,
-    ) -> Self {
-        Self {
-            app,
-            path,
-            cache: defaults.clone().unwrap_or_default(),
-            defaults,
-            serialize_fn,
-            deserialize_fn,
+impl Transaction {
+    fn mark_changed(&mut self, key: &str) {
+        if !self.changed.iter().any(|k| k == key) {
+            self.changed.push(key.to_owned());
         }
     }
 
-    /// Saves the store to disk at the store's `path`.
-    pub fn save(&self) -> crate::Result<()> {
-        fs::create_dir_all(self.path.parent().expect("invalid store path"))?;
-
-        let bytes = (self.serialize_fn)(&self.cache).map_err(crate::Error::Serialize)?;
-        fs::write(&self.path, bytes)?;
-
+    /// Inserts a key-value pair into the store.
+    ///
+    /// If `key` is a JSON Pointer, the nested value it points to is set instead.
+    pub fn set(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<JsonValue>,
+    ) -> crate::Result<()> {
+        let key = key.into();
+        match crate::pointer::parse(&key) {
+            Some(tokens) => {
+                crate::pointer::set(&mut self.cache, &tokens, value.into())?;
+                self.mark_changed(&tokens[0]);
+            }
+            None => {
+                self.expiry.remove(&key);
+                self.mark_changed(&key);
+                self.cache.insert(key, value.into());
+            }
+        }
         Ok(())
     }
 
-    /// Update the store from the on-disk state
-    pub fn load(&mut self) -> crate::Result<()> {
-        let bytes = fs::read(&self.path)?;
-
-        self.cache
-            .extend((self.deserialize_fn)(&bytes).map_err(crate::Error::Deserialize)?);
+    /// Inserts a key-value pair into the store that is removed once `ttl` elapsed,
+    /// see [`Store::set_with_ttl`].
+    ///
+    /// If `key` is a JSON Pointer, the time to live applies to its whole top-level key.
+    pub fn set_with_ttl(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<JsonValue>,
+        ttl: Duration,
+    ) -> crate::Result<()> {
+        let key = key.into();
+        let top_level_key = match crate::pointer::parse(&key) {
+            Some(mut tokens) => tokens.swap_remove(0),
+            None => key.clone(),
+        };
+        self.set(key, value)?;
 
+        let ttl = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+        self.expiry
+            .insert(top_level_key, now_millis().saturating_add(ttl));
         Ok(())
     }
 
-    /// Inserts a key-value pair into the store.
-    pub fn set(&mut self, key: impl Into<String>, value: impl Into<JsonValue>) {
-        let key = key.into();
-        let value = value.into();
-        self.cache.insert(key.clone(), value.clone());
-        let _ = self.emit_change_event(&key, Some(&value));
-    }
-
-    /// Returns a reference to the value corresponding to the key.
+    /// Returns the value for the given `key`, including the changes made in this transaction.
+    ///
+    /// If `key` is a JSON Pointer, the nested value it points to is returned.
     pub fn get(&self, key: impl AsRef<str>) -> Option<&JsonValue> {
-        self.cache.get(key.as_ref())
+        let key = key.as_ref();
+        match crate::pointer::parse(key) {
+            Some(tokens) => crate::pointer::get(&self.cache, &tokens),
+            None => self.cache.get(key),
+        }
     }
 
     /// Returns `true` if the given `key` exists in the store.
     pub fn has(&self, key: impl AsRef<str>) -> bool {
-        self.cache.contains_key(key.as_ref())
+        self.get(key).is_some()
     }
 
     /// Removes a key-value pair from the store.
-    pub fn delete(&mut self, key: impl AsRef<str>) -> bool {
-        let flag = self.cache.remove(key.as_ref()).is_some();
-        if flag {
-            let _ = self.emit_change_event(key.as_ref(), None);
-        }
-        flag
-    }
-
-    /// Clears the store, removing all key-value pairs.
-    ///
-    /// Note: To clear the storage and reset it to its `default` value, use [`reset`](Self::reset) instead.
-    pub fn clear(&mut self) {
-        let keys: Vec<String> = self.cache.keys().cloned().collect();
-        self.cache.clear();
-        for key in &keys {
-            let _ = self.emit_change_event(key, None);
-        }
-    }
-
-    /// Resets the store to its `default` value.
     ///
-    /// If no default value has been set, this method behaves identical to [`clear`](Self::clear).
-    pub fn reset(&mut self) {
-        if let Some(defaults) = &self.defaults {
-            for (key, value) in &self.cache {
-                if defaults.get(key) != Some(value) {
-                    let _ = self.emit_change_event(key, defaults.get(key));
+    /// If `key` is a JSON Pointer, the nested value it points to is removed.
+    pub fn delete(&mut self, key: impl AsRef<str>) -> bool {
+        let key = key.as_ref();
+        match crate::pointer::parse(key) {
+            Some(tokens) => {
+                let deleted = crate::pointer::remove(&mut self.cache, &tokens).is_some();
+                if deleted {
+                    self.mark_changed(&tokens[0]);
                 }
+                deleted
             }
-            for (key, value) in defaults {
-                if !self.cache.contains_key(key) {
-                    let _ = self.emit_change_event(key, Some(value));
+            None => {
+                let deleted = self.cache.remove(key).is_some();
+                if deleted {
+                    self.expiry.remove(key);
+                    self.mark_changed(key);
                 }
+                deleted
             }
-            self.cache.clone_from(defaults);
-        } else {
-            self.clear()
         }
     }
+}
 
-    /// An iterator visiting all keys in arbitrary order.
-    pub fn keys(&self) -> impl Iterator<Item = &String> {
-        self.cache.keys()
-    }
-
-    /// An iterator visiting all values in arbitrary order.
-    pub fn values(&self) -> impl Iterator<Item = &JsonValue> {
-        self.cache.values()
-    }
+enum AutoSaveMessage {
+    Reset,
+    Cancel,
+}
 
-    /// An iterator visiting all key-value pairs in arbitrary order.
-    pub fn entries(&self) -> impl Iterator<Item = (&String, &JsonValue)> {
-        self.cache.iter()
-    }
+/// Debounces the saves of a store after its content changed.
+#[derive(Clone)]
+pub(crate) struct AutoSave {
+    delay: Option<Duration>,
+    debounce_sender: Arc<Mutex<Option<UnboundedSender<AutoSaveMessage>>>>,
+}
 
-    /// Returns the number of elements in the store.
+impl AutoSave {
+    /// Saves `store` once no change happened for the auto save delay.
+    pub(crate) fn trigger<R: Runtime>(
+        &self,
+        store: &Arc<Mutex<StoreInner<R>>>,
+    ) -> crate::Result<()> {
+        let Some(auto_save_delay) = self.delay else {
+            return Ok(());
+        };
+        if auto_save_delay.is_zero() {
+            self.cancel();
+            return store.lock().unwrap().save();
+        }
+        let mut auto_save_debounce_sender = self.debounce_sender.lock().unwrap();
+        if let Some(ref sender) = *auto_save_debounce_sender {
+            let _ = sender.send(AutoSaveMessage::Reset);
+            return Ok(());
+        }
+        let (sender, mut receiver) = unbounded_channel();
+        auto_save_debounce_sender.replace(sender);
+        drop(auto_save_debounce_sender);
+        let store = store.clone();
+        let auto_save_debounce_sender = self.debounce_sender.clone();
+        tauri::async_runtime::spawn(async move {
+            loop {
+                select! {
+                    should_cancel = receiver.recv() => {
+                        if matches!(should_cancel, Some(AutoSaveMessage::Cancel) | None) {
+                            return;
+                        }
+                    }
+                    _ = sleep(auto_save_delay) => {
+                        auto_save_debounce_sender.lock().unwrap().take();
+                        let _ = store.lock().unwrap().save();
+                        return;
+                    }
+                };
+            }
+        });
+        Ok(())
+    }
+
+    /// Cancels the pending save, returns `true` if there was one.
+    fn cancel(&self) -> bool {
+        match self.debounce_sender.lock().unwrap().take() {
+            Some(sender) => {
+                let _ = sender.send(AutoSaveMessage::Cancel);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct StoreInner<R: Runtime> {
+    app: AppHandle<R>,
+    path: PathBuf,
+    cache: HashMap<String, JsonValue>,
+    defaults: Option<HashMap<String, JsonValue>>,
+    serialize_fn: SerializeFn,
+    deserialize_fn: DeserializeFn,
+    backend: Arc<dyn StoreBackend>,
+    validator: Option<Arc<Validator>>,
+    migrations: Arc<[Migration]>,
+    /// Current store version, `None` if the store has no migrations.
+    version: Option<u64>,
+    listeners: Listeners,
+    /// Top-level keys changed since the store was last saved or loaded.
+    changed: HashSet<String>,
+    /// Expiry times of top-level keys, in milliseconds since the unix epoch.
+    expiry: HashMap<String, u64>,
+    /// Wakes up the expiry timer when an entry with a time to live is added.
+    expiry_timer: Arc<Notify>,
+    #[cfg(feature = "watch")]
+    conflict_policy: crate::ConflictPolicy,
+    /// Hash of the persisted content last written or read by this store.
+    #[cfg(feature = "watch")]
+    disk_hash: Option<u64>,
+}
+
+impl<R: Runtime> StoreInner<R> {
+    /// Saves the store with its backend.
+    pub fn save(&mut self) -> crate::Result<()> {
+        if self.backend.per_key() {
+            let mut chunks = Vec::with_capacity(self.changed.len() + 1);
+            for key in &self.changed {
+                let chunk = match self.cache.get(key) {
+                    Some(value) => Some(self.encode_entry(key, value.clone())?),
+                    None => None,
+                };
+                chunks.push((key.clone(), chunk));
+            }
+            if let Some(version) = self.version {
+                let chunk = self.encode_entry(VERSION_KEY, version.into())?;
+                chunks.push((VERSION_KEY.into(), Some(chunk)));
+            }
+            let expiry = match self.expiry_value() {
+                Some(expiry) => Some(self.encode_entry(EXPIRY_KEY, expiry)?),
+                None => None,
+            };
+            chunks.push((EXPIRY_KEY.into(), expiry));
+            self.backend.save_keys(chunks)?;
+            self.synced(&[]);
+        } else {
+            let bytes = if self.version.is_none() && self.expiry.is_empty() {
+                self.encode(&self.cache)?
+            } else {
+                let mut cache = self.cache.clone();
+                if let Some(version) = self.version {
+                    cache.insert(VERSION_KEY.into(), version.into());
+                }
+                if let Some(expiry) = self.expiry_value() {
+                    cache.insert(EXPIRY_KEY.into(), expiry);
+                }
+                self.encode(&cache)?
+            };
+            self.backend.save(&bytes)?;
+            self.synced(&[bytes]);
+        }
+
+        Ok(())
+    }
+
+    /// Update the store from the persisted state, emitting change events for the entries
+    /// whose time to live elapsed.
+    pub fn load(&mut self) -> crate::Result<()> {
+        for key in self.load_persisted()? {
+            let _ = self.emit_change_event(&key, None);
+        }
+        Ok(())
+    }
+
+    /// Update the store from the persisted state.
+    ///
+    /// Returns the keys removed from the store because their time to live elapsed.
+    fn load_persisted(&mut self) -> crate::Result<Vec<String>> {
+        let (mut data, chunks) = match self
+            .backend
+            .load()
+            .and_then(|chunks| Ok((self.decode(&chunks)?, chunks)))
+        {
+            Ok(loaded) => loaded,
+            Err(e) => match self.backend.load_backup() {
+                Ok(Some(chunks)) => {
+                    log::warn!(
+                        "failed to load store {:?}, falling back to backup: {e}",
+                        self.path
+                    );
+                    (self.decode(&chunks)?, chunks)
+                }
+                _ => return Err(e),
+            },
+        };
+
+        let (expiry, expired) = take_expiry(&mut data);
+        let persisted_keys: Vec<String> = data.keys().cloned().collect();
+        let migrated = self.migrate(&mut data)?;
+
+        // the persisted entries take their persisted expiry times, entries only in memory keep theirs
+        let mut merged_expiry = self.expiry.clone();
+        merged_expiry.retain(|key, _| !data.contains_key(key) && !expired.contains(key));
+        merged_expiry.extend(expiry);
+
+        let mut cache = self.cache.clone();
+        cache.extend(data);
+        let purged: Vec<String> = expired
+            .iter()
+            .filter(|key| cache.remove(*key).is_some())
+            .cloned()
+            .collect();
+        self.validate(&cache)?;
+        self.cache = cache;
+        self.expiry = merged_expiry;
+        self.expiry_timer.notify_one();
+        self.synced(&chunks);
+        self.changed.extend(expired);
+
+        if migrated {
+            self.changed.extend(persisted_keys);
+            self.changed.extend(self.cache.keys().cloned());
+            self.save()?;
+        }
+
+        Ok(purged)
+    }
+
+    /// Strips the version from the on-disk `data` and runs all migrations newer than it.
+    ///
+    /// Returns `true` if any migration ran, fails if `data` is newer than the latest migration.
+    fn migrate(&self, data: &mut HashMap<String, JsonValue>) -> crate::Result<bool> {
+        let Some(latest) = self.version else {
+            return Ok(false);
+        };
+        let version = data
+            .remove(VERSION_KEY)
+            .and_then(|v| v.as_u64())
+            .unwrap_or_default();
+        if version > latest {
+            return Err(crate::Error::NewerVersion(version, latest));
+        }
+
+        // the steps run on a copy so a failing step leaves `data` at its persisted version
+        let mut migrated_data = data.clone();
+        let mut migrated = false;
+        for migration in self.migrations.iter().filter(|m| m.version > version) {
+            log::info!(
+                "migrating store {:?} to version {}: {}",
+                self.path,
+                migration.version,
+                migration.description
+            );
+            (migration.migrate)(&mut migrated_data)
+                .map_err(|e| crate::Error::Migration(migration.version, e))?;
+            migrated = true;
+        }
+        *data = migrated_data;
+        Ok(migrated)
+    }
+
+    /// Validates `cache` against the store's schema, if any.
+    fn validate(&self, cache: &HashMap<String, JsonValue>) -> crate::Result<()> {
+        if let Some(validator) = &self.validator {
+            let instance = serde_json::to_value(cache)?;
+            validator
+                .validate(&instance)
+                .map_err(|e| crate::Error::Validation(format!("{e} at \"{}\"", e.instance_path)))?;
+        }
+        Ok(())
+    }
+
+    /// The expiry times as persisted under [`EXPIRY_KEY`], `None` if no entry has a time to live.
+    fn expiry_value(&self) -> Option<JsonValue> {
+        if self.expiry.is_empty() {
+            return None;
+        }
+        Some(JsonValue::Object(
+            self.expiry
+                .iter()
+                .map(|(key, at)| (key.clone(), (*at).into()))
+                .collect(),
+        ))
+    }
+
+    /// Returns `true` if the time to live of the top-level `key` elapsed at `now`.
+    fn is_expired(&self, key: &str, now: u64) -> bool {
+        self.expiry.get(key).is_some_and(|at| *at <= now)
+    }
+
+    /// Removes the entries whose time to live elapsed and emits their change events.
+    ///
+    /// Returns `true` if the store changed and must be saved.
+    fn purge_expired(&mut self) -> bool {
+        let now = now_millis();
+        let expired: Vec<String> = self
+            .expiry
+            .iter()
+            .filter(|(_, at)| **at <= now)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in &expired {
+            self.expiry.remove(key);
+            self.mark_changed(key.clone());
+            if self.cache.remove(key).is_some() {
+                let _ = self.emit_change_event(key, None);
+            }
+        }
+        !expired.is_empty()
+    }
+
+    fn encode(&self, cache: &HashMap<String, JsonValue>) -> crate::Result<Vec<u8>> {
+        (self.serialize_fn)(cache).map_err(|e| codec_error(e, crate::Error::Serialize))
+    }
+
+    /// Serializes a single entry as a store chunk for per key backends.
+    fn encode_entry(&self, key: &str, value: JsonValue) -> crate::Result<Vec<u8>> {
+        self.encode(&HashMap::from([(key.to_owned(), value)]))
+    }
+
+    /// Deserializes and merges the persisted chunks.
+    fn decode(&self, chunks: &[Vec<u8>]) -> crate::Result<HashMap<String, JsonValue>> {
+        let mut data = HashMap::new();
+        for chunk in chunks {
+            data.extend(
+                (self.deserialize_fn)(chunk)
+                    .map_err(|e| codec_error(e, crate::Error::Deserialize))?,
+            );
+        }
+        Ok(data)
+    }
+
+    /// Records that the store matches the persisted `chunks`.
+    #[allow(unused_variables)]
+    fn synced(&mut self, chunks: &[Vec<u8>]) {
+        self.changed.clear();
+        #[cfg(feature = "watch")]
+        {
+            self.disk_hash = Some(hash_chunks(chunks));
+        }
+    }
+
+    /// Records that the top-level `key` has unsaved changes.
+    fn mark_changed(&mut self, key: impl Into<String>) {
+        self.changed.insert(key.into());
+    }
+
+    /// Reloads the store after the store file was modified by someone else.
+    ///
+    /// Does nothing if the file content is the one last written or read by this store.
+    /// Returns `true` if the store still has unsaved changes, which must be auto saved.
+    #[cfg(feature = "watch")]
+    pub(crate) fn reload_external(&mut self) -> crate::Result<bool> {
+        let chunks = match self.backend.load() {
+            Ok(chunks) => chunks,
+            Err(crate::Error::Io(e)) if e.kind() == std::io::ErrorKind::NotFound => {
+                return Ok(false)
+            }
+            Err(e) => return Err(e),
+        };
+        let hash = hash_chunks(&chunks);
+        if self.disk_hash == Some(hash) {
+            return Ok(false);
+        }
+
+        let mut data = self.decode(&chunks)?;
+        let (disk_expiry, _) = take_expiry(&mut data);
+        let migrated = self.migrate(&mut data)?;
+        let mut disk = self.defaults.clone().unwrap_or_default();
+        disk.extend(data);
+
+        let dirty = !self.changed.is_empty();
+        let keep_changes =
+            dirty && !matches!(self.conflict_policy, crate::ConflictPolicy::DiskWins);
+        let cache = if dirty {
+            match &self.conflict_policy {
+                crate::ConflictPolicy::DiskWins => disk.clone(),
+                crate::ConflictPolicy::MemoryWins => {
+                    // only the keys changed since the last save override the file content
+                    let mut cache = disk.clone();
+                    for key in &self.changed {
+                        match self.cache.get(key) {
+                            Some(value) => cache.insert(key.clone(), value.clone()),
+                            None => cache.remove(key),
+                        };
+                    }
+                    cache
+                }
+                crate::ConflictPolicy::Callback(resolve) => resolve(&self.cache, &disk),
+            }
+        } else {
+            disk.clone()
+        };
+        self.validate(&cache)?;
+
+        let previous = std::mem::replace(&mut self.cache, cache);
+        let mut expiry = disk_expiry;
+        if keep_changes {
+            for key in &self.changed {
+                match self.expiry.get(key) {
+                    Some(at) => expiry.insert(key.clone(), *at),
+                    None => expiry.remove(key),
+                };
+            }
+        }
+        expiry.retain(|key, _| self.cache.contains_key(key));
+        self.expiry = expiry;
+        self.expiry_timer.notify_one();
+        self.disk_hash = Some(hash);
+        self.changed = self
+            .cache
+            .keys()
+            .chain(disk.keys())
+            .filter(|key| self.cache.get(*key) != disk.get(*key))
+            .cloned()
+            .collect();
+
+        for (key, value) in &self.cache {
+            if previous.get(key) != Some(value) {
+                let _ = self.emit_change_event(key, Some(value));
+            }
+        }
+        for key in previous.keys() {
+            if !self.cache.contains_key(key) {
+                let _ = self.emit_change_event(key, None);
+            }
+        }
+
+        if migrated {
+            self.save()?;
+        }
+
+        Ok(!self.changed.is_empty())
+    }
+
+    /// Inserts a key-value pair into the store.
+    ///
+    /// If `key` is a JSON Pointer, the nested value it points to is set instead.
+    pub fn set(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<JsonValue>,
+    ) -> crate::Result<()> {
+        self.purge_expired();
+
+        let key = key.into();
+        let value = value.into();
+        let tokens = crate::pointer::parse(&key);
+        let top_level_key = match &tokens {
+            Some(tokens) => tokens[0].clone(),
+            None => key.clone(),
+        };
+
+        let previous = self.cache.get(&top_level_key).cloned();
+        let result = match &tokens {
+            Some(tokens) => crate::pointer::set(&mut self.cache, tokens, value.clone()),
+            None => {
+                self.cache.insert(key.clone(), value.clone());
+                Ok(())
+            }
+        }
+        .and_then(|_| self.validate(&self.cache));
+        if let Err(e) = result {
+            match previous {
+                Some(previous) => self.cache.insert(top_level_key, previous),
+                None => self.cache.remove(&top_level_key),
+            };
+            return Err(e);
+        }
+
+        if tokens.is_none() {
+            self.expiry.remove(&top_level_key);
+        }
+        let _ = self.emit_change_event(&key, Some(&value));
+        if tokens.is_some() {
+            // listeners of the top-level key see the nested change too
+            let _ = self.emit_change_event(&top_level_key, self.cache.get(&top_level_key));
+        }
+        self.mark_changed(top_level_key);
+        Ok(())
+    }
+
+    /// Inserts a key-value pair into the store that is removed once `ttl` elapsed.
+    ///
+    /// If `key` is a JSON Pointer, the time to live applies to its whole top-level key.
+    pub fn set_with_ttl(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<JsonValue>,
+        ttl: Duration,
+    ) -> crate::Result<()> {
+        let key = key.into();
+        let top_level_key = match crate::pointer::parse(&key) {
+            Some(mut tokens) => tokens.swap_remove(0),
+            None => key.clone(),
+        };
+        self.set(key, value)?;
+
+        let ttl = u64::try_from(ttl.as_millis()).unwrap_or(u64::MAX);
+        self.expiry
+            .insert(top_level_key, now_millis().saturating_add(ttl));
+        self.expiry_timer.notify_one();
+        Ok(())
+    }
+
+    /// Returns a reference to the value corresponding to the key.
+    ///
+    /// If `key` is a JSON Pointer, the nested value it points to is returned.
+    pub fn get(&self, key: impl AsRef<str>) -> Option<&JsonValue> {
+        let key = key.as_ref();
+        let tokens = crate::pointer::parse(key);
+        let top_level_key = tokens.as_ref().map_or(key, |tokens| tokens[0].as_str());
+        if self.is_expired(top_level_key, now_millis()) {
+            return None;
+        }
+        match tokens {
+            Some(tokens) => crate::pointer::get(&self.cache, &tokens),
+            None => self.cache.get(key),
+        }
+    }
+
+    /// Returns `true` if the given `key` exists in the store.
+    pub fn has(&self, key: impl AsRef<str>) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Removes a key-value pair from the store.
+    ///
+    /// If `key` is a JSON Pointer, the nested value it points to is removed.
+    pub fn delete(&mut self, key: impl AsRef<str>) -> crate::Result<bool> {
+        self.purge_expired();
+
+        let key = key.as_ref();
+        let tokens = crate::pointer::parse(key);
+        let top_level_key = match &tokens {
+            Some(tokens) => tokens[0].clone(),
+            None => key.to_owned(),
+        };
+
+        let previous = self.cache.get(&top_level_key).cloned();
+        let deleted = match &tokens {
+            Some(tokens) => crate::pointer::remove(&mut self.cache, tokens).is_some(),
+            None => self.cache.remove(key).is_some(),
+        };
+        if !deleted {
+            return Ok(false);
+        }
+        if let Err(e) = self.validate(&self.cache) {
+            if let Some(previous) = previous {
+                self.cache.insert(top_level_key, previous);
+            }
+            return Err(e);
+        }
+
+        if tokens.is_none() {
+            self.expiry.remove(key);
+        }
+        let _ = self.emit_change_event(key, None);
+        if tokens.is_some() {
+            // listeners of the top-level key see the nested change too
+            let _ = self.emit_change_event(&top_level_key, self.cache.get(&top_level_key));
+        }
+        self.mark_changed(top_level_key);
+        Ok(true)
+    }
+
+    /// Clears the store, removing all key-value pairs.
+    ///
+    /// Note: To clear the storage and reset it to its `default` value, use [`reset`](Self::reset) instead.
+    pub fn clear(&mut self) -> crate::Result<()> {
+        self.validate(&HashMap::new())?;
+
+        let keys: Vec<String> = self.cache.keys().cloned().collect();
+        self.cache.clear();
+        self.expiry.clear();
+        self.changed.extend(keys.iter().cloned());
+        for key in &keys {
+            let _ = self.emit_change_event(key, None);
+        }
+        Ok(())
+    }
+
+    /// Resets the store to its `default` value.
+    ///
+    /// If no default value has been set, this method behaves identical to [`clear`](Self::clear).
+    pub fn reset(&mut self) -> crate::Result<()> {
+        if let Some(defaults) = &self.defaults {
+            self.validate(defaults)?;
+
+            for (key, value) in &self.cache {
+                if defaults.get(key) != Some(value) {
+                    let _ = self.emit_change_event(key, defaults.get(key));
+                }
+            }
+            for (key, value) in defaults {
+                if !self.cache.contains_key(key) {
+                    let _ = self.emit_change_event(key, Some(value));
+                }
+            }
+            self.changed
+                .extend(self.cache.keys().chain(defaults.keys()).cloned());
+            self.cache.clone_from(defaults);
+            self.expiry.clear();
+            Ok(())
+        } else {
+            self.clear()
+        }
+    }
+
+    /// An iterator visiting all keys in arbitrary order.
+    pub fn keys(&self) -> impl Iterator<Item = &String> {
+        self.entries().map(|(key, _)| key)
+    }
+
+    /// An iterator visiting all values in arbitrary order.
+    pub fn values(&self) -> impl Iterator<Item = &JsonValue> {
+        self.entries().map(|(_, value)| value)
+    }
+
+    /// An iterator visiting all key-value pairs in arbitrary order.
+    pub fn entries(&self) -> impl Iterator<Item = (&String, &JsonValue)> {
+        let now = now_millis();
+        self.cache
+            .iter()
+            .filter(move |(key, _)| !self.is_expired(key, now))
+    }
+
+    /// Returns the number of elements in the store.
     pub fn len(&self) -> usize {
-        self.cache.len()
+        self.entries().count()
     }
 
     /// Returns true if the store contains no elements.
     pub fn is_empty(&self) -> bool {
-        self.cache.is_empty()
+        self.entries().next().is_none()
+    }
+
+    /// Applies the changes of a [`Transaction`] and emits a single `store://batch-change` event,
+    /// the change listeners are notified of every changed key.
+    ///
+    /// Returns `true` if the store content or the expiry times changed.
+    fn commit(&mut self, tx: Transaction) -> crate::Result<bool> {
+        self.validate(&tx.cache)?;
+
+        let changed_keys: Vec<String> = tx
+            .changed
+            .into_iter()
+            .filter(|key| self.cache.get(key) != tx.cache.get(key))
+            .collect();
+        let expiry_changed = self.expiry != tx.expiry;
+        self.cache = tx.cache;
+        if expiry_changed {
+            let previous = std::mem::replace(&mut self.expiry, tx.expiry);
+            let keys: Vec<String> = previous
+                .keys()
+                .chain(self.expiry.keys())
+                .filter(|key| previous.get(*key) != self.expiry.get(*key))
+                .cloned()
+                .collect();
+            self.changed.extend(keys);
+            self.expiry_timer.notify_one();
+        }
+
+        if changed_keys.is_empty() {
+            return Ok(expiry_changed);
+        }
+        self.changed.extend(changed_keys.iter().cloned());
+        let changes = changed_keys
+            .iter()
+            .map(|key| {
+                let value = self.cache.get(key);
+                KeyChange {
+                    key,
+                    value,
+                    exists: value.is_some(),
+                }
+            })
+            .collect();
+        let _ = self.emit_batch_change_event(changes);
+
+        Ok(true)
     }
 
     fn emit_change_event(&self, key: &str, value: Option<&JsonValue>) -> crate::Result<()> {
+        self.listeners.notify(key, value);
+
         let state = self.app.state::<StoreState>();
         let stores = state.stores.lock().unwrap();
         let exists = value.is_some();
@@ -393,6 +1444,24 @@ impl<R: Runtime> StoreInner<R> {
         )?;
         Ok(())
     }
+
+    fn emit_batch_change_event(&self, changes: Vec<KeyChange<'_>>) -> crate::Result<()> {
+        for change in &changes {
+            self.listeners.notify(change.key, change.value);
+        }
+
+        let state = self.app.state::<StoreState>();
+        let stores = state.stores.lock().unwrap();
+        self.app.emit(
+            "store://batch-change",
+            BatchChangePayload {
+                path: &self.path,
+                resource_id: stores.get(&self.path).copied(),
+                changes,
+            },
+        )?;
+        Ok(())
+    }
 }
 
 impl<R: Runtime> std::fmt::Debug for StoreInner<R> {
@@ -404,10 +1473,17 @@ impl<R: Runtime> std::fmt::Debug for StoreInner<R> {
     }
 }
 
+/// A key-value store persisted to disk.
+///
+/// Keys starting with `/` are treated as [JSON Pointers](https://datatracker.ietf.org/doc/html/rfc6901)
+/// by [`Store::get`], [`Store::set`], [`Store::has`] and [`Store::delete`],
+/// where the first reference token is the top-level key, e.g. `/editor/font/size`.
+/// A top-level key that starts with `/` can be addressed as `/~1key`.
 pub struct Store<R: Runtime> {
-    auto_save: Option<Duration>,
-    auto_save_debounce_sender: Arc<Mutex<Option<UnboundedSender<AutoSaveMessage>>>>,
+    auto_save: AutoSave,
     store: Arc<Mutex<StoreInner<R>>>,
+    #[cfg(feature = "watch")]
+    _watcher: Option<notify::RecommendedWatcher>,
 }
 
 impl<R: Runtime> Resource for Store<R> {
@@ -420,17 +1496,84 @@ impl<R: Runtime> Resource for Store<R> {
 }
 
 impl<R: Runtime> Store<R> {
-    // /// Do something with the inner store,
-    // /// useful for batching some work if you need higher performance
-    // pub fn with_store<T>(&self, f: impl FnOnce(&mut StoreInner<R>) -> T) -> T {
-    //     let mut store = self.store.lock().unwrap();
-    //     f(&mut store)
-    // }
+    /// Applies several changes to the store atomically.
+    ///
+    /// The closure works on a copy of the store content. If it returns an error, or the result
+    /// does not match the store's schema, nothing is applied. Otherwise all changes are committed
+    /// at once, a single `store://batch-change` event is emitted and auto save is triggered once.
+    ///
+    /// The store is locked while the closure runs, so it must not call other methods on this store.
+    ///
+    /// # Examples
+    /// ```
+    /// use tauri_plugin_store::StoreExt;
+    ///
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = app.store("store.json")?;
+    ///     store.transaction(|tx| {
+    ///       tx.set("width", 800)?;
+    ///       tx.set("height", 600)?;
+    ///       tx.delete("maximized");
+    ///       Ok(())
+    ///     })?;
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn transaction<T>(
+        &self,
+        f: impl FnOnce(&mut Transaction) -> crate::Result<T>,
+    ) -> crate::Result<T> {
+        let mut store = self.store.lock().unwrap();
+        let purged = store.purge_expired();
+        let mut tx = Transaction {
+            cache: store.cache.clone(),
+            expiry: store.expiry.clone(),
+            changed: Vec::new(),
+        };
+        let result = f(&mut tx).and_then(|output| Ok((output, store.commit(tx)?)));
+        drop(store);
+
+        if purged || matches!(result, Ok((_, true))) {
+            let _ = self.trigger_auto_save();
+        }
+        result.map(|(output, _)| output)
+    }
 
     /// Inserts a key-value pair into the store.
-    pub fn set(&self, key: impl Into<String>, value: impl Into<JsonValue>) {
-        self.store.lock().unwrap().set(key.into(), value.into());
+    ///
+    /// Fails with [`crate::Error::Validation`] if the store has a schema and the new value does not match it.
+    pub fn set(&self, key: impl Into<String>, value: impl Into<JsonValue>) -> crate::Result<()> {
+        self.store.lock().unwrap().set(key.into(), value.into())?;
+        let _ = self.trigger_auto_save();
+        Ok(())
+    }
+
+    /// Inserts a key-value pair into the store that is removed once `ttl` elapsed.
+    ///
+    /// The expiry time is persisted with the store. Expired entries are hidden from
+    /// [`Self::get`], [`Self::has`], [`Self::keys`] and [`Self::entries`], and are removed
+    /// in the background, emitting a `store://change` event with `exists: false`.
+    ///
+    /// Setting the key again without a time to live makes it permanent.
+    pub fn set_with_ttl(
+        &self,
+        key: impl Into<String>,
+        value: impl Into<JsonValue>,
+        ttl: Duration,
+    ) -> crate::Result<()> {
+        self.store
+            .lock()
+            .unwrap()
+            .set_with_ttl(key.into(), value.into(), ttl)?;
         let _ = self.trigger_auto_save();
+        Ok(())
+    }
+
+    /// Serializes `value` and inserts it into the store.
+    pub fn set_typed<T: Serialize>(&self, key: impl Into<String>, value: &T) -> crate::Result<()> {
+        self.set(key, serde_json::to_value(value)?)
     }
 
     /// Returns the value for the given `key` or `None` if the key does not exist.
@@ -438,15 +1581,29 @@ impl<R: Runtime> Store<R> {
         self.store.lock().unwrap().get(key).cloned()
     }
 
+    /// Returns the value for the given `key` deserialized as `T`, or `None` if the key does not exist.
+    pub fn get_typed<T: DeserializeOwned>(&self, key: impl AsRef<str>) -> crate::Result<Option<T>> {
+        self.get(key)
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(Into::into)
+    }
+
     /// Returns `true` if the given `key` exists in the store.
     pub fn has(&self, key: impl AsRef<str>) -> bool {
         self.store.lock().unwrap().has(key)
     }
 
     /// Removes a key-value pair from the store.
-    pub fn delete(&self, key: impl AsRef<str>) -> bool {
-        let deleted = self.store.lock().unwrap().delete(key);
-        if deleted {
+    ///
+    /// Fails with [`crate::Error::Validation`] if the store has a schema that requires the key.
+    pub fn delete(&self, key: impl AsRef<str>) -> crate::Result<bool> {
+        let mut store = self.store.lock().unwrap();
+        let purged = store.purge_expired();
+        let deleted = store.delete(key);
+        drop(store);
+
+        if purged || matches!(deleted, Ok(true)) {
             let _ = self.trigger_auto_save();
         }
         deleted
@@ -455,17 +1612,19 @@ impl<R: Runtime> Store<R> {
     /// Clears the store, removing all key-value pairs.
     ///
     /// Note: To clear the storage and reset it to its `default` value, use [`reset`](Self::reset) instead.
-    pub fn clear(&self) {
-        self.store.lock().unwrap().clear();
+    pub fn clear(&self) -> crate::Result<()> {
+        self.store.lock().unwrap().clear()?;
         let _ = self.trigger_auto_save();
+        Ok(())
     }
 
     /// Resets the store to its `default` value.
     ///
     /// If no default value has been set, this method behaves identical to [`clear`](Self::clear).
-    pub fn reset(&self) {
-        self.store.lock().unwrap().reset();
+    pub fn reset(&self) -> crate::Result<()> {
+        self.store.lock().unwrap().reset()?;
         let _ = self.trigger_auto_save();
+        Ok(())
     }
 
     /// Returns a list of all keys in the store.
@@ -505,12 +1664,68 @@ impl<R: Runtime> Store<R> {
 
     /// Saves the store to disk at the store's `path`.
     pub fn save(&self) -> crate::Result<()> {
-        if let Some(sender) = self.auto_save_debounce_sender.lock().unwrap().take() {
-            let _ = sender.send(AutoSaveMessage::Cancel);
-        }
+        self.auto_save.cancel();
         self.store.lock().unwrap().save()
     }
 
+    /// Calls `f` whenever a key matching `pattern` changes, with the new value or `None` if it was deleted.
+    ///
+    /// Returns an id that can be passed to [`Self::unlisten`].
+    ///
+    /// # Examples
+    /// ```
+    /// use tauri_plugin_store::StoreExt;
+    ///
+    /// tauri::Builder::default()
+    ///   .plugin(tauri_plugin_store::Builder::default().build())
+    ///   .setup(|app| {
+    ///     let store = app.store("settings.json")?;
+    ///     store.on_change("editor.*", |key, value| {
+    ///       println!("{key} changed to {value:?}");
+    ///     });
+    ///     Ok(())
+    ///   });
+    /// ```
+    pub fn on_change<F>(&self, pattern: impl Into<KeyPattern>, f: F) -> u32
+    where
+        F: Fn(&str, Option<&JsonValue>) + Send + Sync + 'static,
+    {
+        let listeners = self.store.lock().unwrap().listeners.clone();
+        listeners.add_callback(pattern.into(), f)
+    }
+
+    /// Sends the changes of keys matching `pattern` to the given channel.
+    ///
+    /// Returns an id that can be passed to [`Self::unlisten`].
+    pub fn watch(&self, pattern: impl Into<KeyPattern>, channel: Channel<StoreChange>) -> u32 {
+        let listeners = self.store.lock().unwrap().listeners.clone();
+        listeners.add_channel(pattern.into(), channel, None)
+    }
+
+    /// Like [`Self::watch`] for a channel of the webview with the given label,
+    /// the listener is removed when the webview is destroyed.
+    pub(crate) fn watch_webview(
+        &self,
+        webview: &str,
+        pattern: KeyPattern,
+        channel: Channel<StoreChange>,
+    ) -> u32 {
+        let listeners = self.store.lock().unwrap().listeners.clone();
+        listeners.add_channel(pattern, channel, Some(webview.into()))
+    }
+
+    /// Removes the listeners registered by the webview with the given label.
+    pub(crate) fn unlisten_webview(&self, webview: &str) {
+        let listeners = self.store.lock().unwrap().listeners.clone();
+        listeners.remove_webview(webview);
+    }
+
+    /// Removes a listener registered with [`Self::on_change`] or [`Self::watch`].
+    pub fn unlisten(&self, id: u32) -> bool {
+        let listeners = self.store.lock().unwrap().listeners.clone();
+        listeners.remove(id)
+    }
+
     /// Removes the store from the resource table
     pub fn close_resource(&self) {
         let store = self.store.lock().unwrap();
@@ -525,45 +1740,12 @@ impl<R: Runtime> Store<R> {
     }
 
     fn trigger_auto_save(&self) -> crate::Result<()> {
-        let Some(auto_save_delay) = self.auto_save else {
-            return Ok(());
-        };
-        if auto_save_delay.is_zero() {
-            return self.save();
-        }
-        let mut auto_save_debounce_sender = self.auto_save_debounce_sender.lock().unwrap();
-        if let Some(ref sender) = *auto_save_debounce_sender {
-            let _ = sender.send(AutoSaveMessage::Reset);
-            return Ok(());
-        }
-        let (sender, mut receiver) = unbounded_channel();
-        auto_save_debounce_sender.replace(sender);
-        drop(auto_save_debounce_sender);
-        let store = self.store.clone();
-        let auto_save_debounce_sender = self.auto_save_debounce_sender.clone();
-        tauri::async_runtime::spawn(async move {
-            loop {
-                select! {
-                    should_cancel = receiver.recv() => {
-                        if matches!(should_cancel, Some(AutoSaveMessage::Cancel) | None) {
-                            return;
-                        }
-                    }
-                    _ = sleep(auto_save_delay) => {
-                        auto_save_debounce_sender.lock().unwrap().take();
-                        let _ = store.lock().unwrap().save();
-                        return;
-                    }
-                };
-            }
-        });
-        Ok(())
+        self.auto_save.trigger(&self.store)
     }
 
     fn apply_pending_auto_save(&self) {
         // Cancel and save if auto save is pending
-        if let Some(sender) = self.auto_save_debounce_sender.lock().unwrap().take() {
-            let _ = sender.send(AutoSaveMessage::Cancel);
+        if self.auto_save.cancel() {
             let _ = self.save();
         };
     }
@@ -574,3 +1756,453 @@ impl<R: Runtime> Drop for Store<R> {
         self.apply_pending_auto_save();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MemoryBackend;
+    use serde_json::json;
+    use tauri::{
+        test::{mock_builder, mock_context, noop_assets, MockRuntime},
+        Listener,
+    };
+
+    fn app() -> tauri::App<MockRuntime> {
+        mock_builder()
+            .plugin(crate::Builder::default().build())
+            .build(mock_context(noop_assets()))
+            .unwrap()
+    }
+
+    fn backend(data: JsonValue) -> MemoryBackend {
+        MemoryBackend::with_data(serde_json::to_vec(&data).unwrap())
+    }
+
+    fn saved(backend: &MemoryBackend) -> JsonValue {
+        serde_json::from_slice(&backend.data().unwrap()).unwrap()
+    }
+
+    /// A migration recording its version in the `steps` array.
+    fn step(version: u64) -> Migration {
+        Migration::new(version, "record the version", move |cache| {
+            cache
+                .entry("steps".into())
+                .or_insert_with(|| json!([]))
+                .as_array_mut()
+                .ok_or("steps is not an array")?
+                .push(version.into());
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn migrations_run_in_order() {
+        let app = app();
+        let backend = backend(json!({ VERSION_KEY: 1, "steps": [1] }));
+        let store = StoreBuilder::new(&app, "store.json")
+            .backend(backend.clone())
+            .migrations(vec![step(3), step(1), step(2)])
+            .build()
+            .unwrap();
+
+        assert_eq!(store.get("steps"), Some(json!([1, 2, 3])));
+        assert_eq!(store.get(VERSION_KEY), None);
+        assert_eq!(
+            saved(&backend),
+            json!({ VERSION_KEY: 3, "steps": [1, 2, 3] })
+        );
+    }
+
+    #[test]
+    fn invalid_data_is_not_overwritten() {
+        let app = app();
+        let data = json!({ "volume": "loud" });
+        let backend = backend(data.clone());
+        let result = StoreBuilder::new(&app, "store.json")
+            .backend(backend.clone())
+            .schema(json!({ "type": "object", "properties": { "volume": { "type": "number" } } }))
+            .default("volume", 5)
+            .build();
+
+        assert!(matches!(result, Err(crate::Error::Validation(_))));
+        assert_eq!(saved(&backend), data);
+    }
+
+    #[test]
+    fn failed_migration_is_not_saved() {
+        let app = app();
+        let data = json!({ VERSION_KEY: 1, "steps": [1] });
+        let backend = backend(data.clone());
+        let failing = Migration::new(3, "fail", |_| Err("unsupported data".into()));
+        let result = StoreBuilder::new(&app, "store.json")
+            .backend(backend.clone())
+            .migrations(vec![step(1), step(2), failing])
+            .build();
+
+        assert!(matches!(result, Err(crate::Error::Migration(3, _))));
+        assert_eq!(saved(&backend), data);
+    }
+
+    #[test]
+    fn newer_version_is_not_overwritten() {
+        let app = app();
+        let data = json!({ VERSION_KEY: 3, "steps": [1, 2, 3] });
+        let backend = backend(data.clone());
+        let result = StoreBuilder::new(&app, "store.json")
+            .backend(backend.clone())
+            .migrations(vec![step(1), step(2)])
+            .build();
+
+        assert!(matches!(result, Err(crate::Error::NewerVersion(3, 2))));
+        assert_eq!(saved(&backend), data);
+    }
+
+    type Recorded<T> = Arc<Mutex<Vec<T>>>;
+
+    /// Records the changes sent to the store listeners and the keys of the `store://change` events.
+    fn record_changes(
+        app: &tauri::App<MockRuntime>,
+        store: &Store<MockRuntime>,
+    ) -> (Recorded<JsonValue>, Recorded<String>) {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        store.watch(
+            KeyPattern::Any,
+            Channel::new({
+                let changes = changes.clone();
+                move |body| {
+                    if let tauri::ipc::InvokeResponseBody::Json(change) = body {
+                        changes
+                            .lock()
+                            .unwrap()
+                            .push(serde_json::from_str(&change).unwrap());
+                    }
+                    Ok(())
+                }
+            }),
+        );
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        app.listen_any("store://change", {
+            let events = events.clone();
+            move |event| {
+                let payload: JsonValue = serde_json::from_str(event.payload()).unwrap();
+                events
+                    .lock()
+                    .unwrap()
+                    .push(payload["key"].as_str().unwrap().to_owned());
+            }
+        });
+
+        (changes, events)
+    }
+
+    #[test]
+    fn transaction_commits_changes() {
+        let app = app();
+        let backend = backend(json!({ "a": 1, "b": 2, "editor": { "font": "mono" } }));
+        let store = StoreBuilder::new(&app, "store.json")
+            .backend(backend.clone())
+            .disable_auto_save()
+            .build()
+            .unwrap();
+        let (changes, events) = record_changes(&app, &store);
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        app.listen_any("store://batch-change", {
+            let batches = batches.clone();
+            move |event| {
+                let payload: JsonValue = serde_json::from_str(event.payload()).unwrap();
+                batches.lock().unwrap().push(payload["changes"].clone());
+            }
+        });
+
+        store
+            .transaction(|tx| {
+                tx.set("a", 10)?;
+                tx.set("/editor/size", 12)?;
+                tx.set_with_ttl("token", "secret", Duration::from_secs(60))?;
+                assert!(tx.delete("b"));
+                assert!(!tx.delete("/editor/missing"));
+                assert_eq!(tx.get("/editor/size"), Some(&json!(12)));
+                Ok(())
+            })
+            .unwrap();
+
+        assert_eq!(store.get("a"), Some(json!(10)));
+        assert_eq!(store.get("b"), None);
+        assert_eq!(
+            store.get("editor"),
+            Some(json!({ "font": "mono", "size": 12 }))
+        );
+        assert_eq!(store.get("token"), Some(json!("secret")));
+        assert!(store.store.lock().unwrap().expiry.contains_key("token"));
+        assert!(events.lock().unwrap().is_empty());
+        assert_eq!(
+            *batches.lock().unwrap(),
+            [json!([
+                { "key": "a", "value": 10, "exists": true },
+                { "key": "editor", "value": { "font": "mono", "size": 12 }, "exists": true },
+                { "key": "token", "value": "secret", "exists": true },
+                { "key": "b", "value": null, "exists": false },
+            ])]
+        );
+        assert_eq!(
+            *changes.lock().unwrap(),
+            [
+                json!({ "key": "a", "value": 10, "exists": true }),
+                json!({ "key": "editor", "value": { "font": "mono", "size": 12 }, "exists": true }),
+                json!({ "key": "token", "value": "secret", "exists": true }),
+                json!({ "key": "b", "value": null, "exists": false }),
+            ]
+        );
+    }
+
+    #[test]
+    fn transaction_rolls_back() {
+        let app = app();
+        let data = json!({ "a": 1, "list": [1] });
+        let backend = backend(data.clone());
+        let store = StoreBuilder::new(&app, "store.json")
+            .backend(backend.clone())
+            .schema(json!({ "type": "object", "properties": { "a": { "type": "number" } } }))
+            .build()
+            .unwrap();
+        let (changes, events) = record_changes(&app, &store);
+
+        // an error of the closure discards the changes made before it
+        let result = store.transaction(|tx| {
+            tx.set("a", 2)?;
+            tx.set("/list/5", 1)
+        });
+        assert!(matches!(result, Err(crate::Error::InvalidKeyPath(_))));
+
+        // so does a result that does not match the schema
+        let result = store.transaction(|tx| {
+            tx.set("list", json!([]))?;
+            tx.set("a", "text")
+        });
+        assert!(matches!(result, Err(crate::Error::Validation(_))));
+
+        assert_eq!(store.get("a"), Some(json!(1)));
+        assert_eq!(store.get("list"), Some(json!([1])));
+        assert!(changes.lock().unwrap().is_empty());
+        assert!(events.lock().unwrap().is_empty());
+        store.save().unwrap();
+        assert_eq!(saved(&backend), data);
+    }
+
+    #[cfg(feature = "watch")]
+    #[test]
+    fn external_changes_are_reloaded() {
+        let app = app();
+        let backend = backend(json!({ "a": 1 }));
+        let store = StoreBuilder::new(&app, "store.json")
+            .backend(backend.clone())
+            .disable_auto_save()
+            .on_conflict(crate::ConflictPolicy::MemoryWins)
+            .default("z", 0)
+            .build()
+            .unwrap();
+        store.set("b", 2).unwrap();
+        store.save().unwrap();
+        let entries = |store: &Store<MockRuntime>| {
+            json!(store.entries().into_iter().collect::<HashMap<_, _>>())
+        };
+
+        // without unsaved changes the store is the file content on top of the defaults,
+        // so a key deleted externally is deleted in the store too
+        backend.save(br#"{"a":2}"#).unwrap();
+        assert!(!store.store.lock().unwrap().reload_external().unwrap());
+        assert_eq!(entries(&store), json!({ "a": 2, "z": 0 }));
+
+        // only the unsaved changes are kept, and they must be saved
+        store.set("c", 3).unwrap();
+        store.delete("z").unwrap();
+        backend.save(br#"{"d":4,"z":5}"#).unwrap();
+        assert!(store.store.lock().unwrap().reload_external().unwrap());
+        assert_eq!(entries(&store), json!({ "c": 3, "d": 4 }));
+    }
+
+    #[test]
+    fn nested_changes_notify_top_level_key() {
+        let app = app();
+        let store = StoreBuilder::new(&app, "store.json")
+            .backend(backend(json!({ "editor": { "font": "mono" } })))
+            .disable_auto_save()
+            .build()
+            .unwrap();
+        let (changes, events) = record_changes(&app, &store);
+
+        store.set("/editor/size", 12).unwrap();
+        store.delete("/editor/font").unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            ["/editor/size", "editor", "/editor/font", "editor"]
+        );
+        assert_eq!(
+            *changes.lock().unwrap(),
+            [
+                json!({ "key": "/editor/size", "value": 12, "exists": true }),
+                json!({ "key": "editor", "value": { "font": "mono", "size": 12 }, "exists": true }),
+                json!({ "key": "/editor/font", "value": null, "exists": false }),
+                json!({ "key": "editor", "value": { "size": 12 }, "exists": true }),
+            ]
+        );
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[test]
+    fn sqlite_saves_changed_keys() {
+        let app = app();
+        let path = std::env::temp_dir().join(format!(
+            "tauri-plugin-store-test-{}.sqlite",
+            std::process::id()
+        ));
+        let _ = std::fs::remove_file(&path);
+        let store = StoreBuilder::new(&app, "store.sqlite")
+            .backend(crate::SqliteBackend::open(&path).unwrap())
+            .disable_auto_save()
+            .build()
+            .unwrap();
+
+        let db = rusqlite::Connection::open(&path).unwrap();
+        let rows = || -> JsonValue {
+            let mut statement = db.prepare("SELECT key, value FROM store").unwrap();
+            let rows = statement
+                .query_map((), |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, Vec<u8>>(1)?))
+                })
+                .unwrap()
+                .map(|row| {
+                    let (key, value) = row.unwrap();
+                    (key, serde_json::from_slice(&value).unwrap())
+                })
+                .collect::<serde_json::Map<String, JsonValue>>();
+            rows.into()
+        };
+
+        store.set("a", 1).unwrap();
+        store.set("b", 2).unwrap();
+        store.save().unwrap();
+        assert_eq!(rows(), json!({ "a": { "a": 1 }, "b": { "b": 2 } }));
+
+        // the rows of unchanged keys are not written again
+        db.execute(
+            "UPDATE store SET value = ?1 WHERE key = 'a'",
+            (br#"{"a":10}"#,),
+        )
+        .unwrap();
+        store.set("b", 3).unwrap();
+        store.save().unwrap();
+        assert_eq!(rows(), json!({ "a": { "a": 10 }, "b": { "b": 3 } }));
+
+        store.delete("b").unwrap();
+        store.save().unwrap();
+        assert_eq!(rows(), json!({ "a": { "a": 10 } }));
+
+        drop(store);
+        drop(db);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[test]
+    fn expired_entries_are_saved() {
+        let app = app();
+        let backend = backend(json!({ "a": 1 }));
+        let store = StoreBuilder::new(&app, "store.json")
+            .backend(backend.clone())
+            .auto_save(Duration::ZERO)
+            .build()
+            .unwrap();
+        let (changes, _) = record_changes(&app, &store);
+
+        store
+            .set_with_ttl("token", "secret", Duration::from_millis(50))
+            .unwrap();
+        let saved_expiry = saved(&backend)[EXPIRY_KEY]["token"].as_u64().unwrap();
+        assert!(saved_expiry > now_millis());
+
+        // the expiry timer removes the entry and saves the store
+        for _ in 0..100 {
+            if saved(&backend) == json!({ "a": 1 }) {
+                break;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert_eq!(saved(&backend), json!({ "a": 1 }));
+        assert_eq!(store.get("token"), None);
+        assert_eq!(
+            changes.lock().unwrap().last(),
+            Some(&json!({ "key": "token", "value": null, "exists": false }))
+        );
+    }
+
+    #[test]
+    fn reload_keeps_memory_expiry() {
+        let app = app();
+        let backend = backend(json!({ "a": 1 }));
+        let store = StoreBuilder::new(&app, "store.json")
+            .backend(backend.clone())
+            .disable_auto_save()
+            .build()
+            .unwrap();
+        store
+            .set_with_ttl("token", "secret", Duration::from_secs(3600))
+            .unwrap();
+        store.save().unwrap();
+        store
+            .set_with_ttl("session", 1, Duration::from_secs(3600))
+            .unwrap();
+        let (changes, events) = record_changes(&app, &store);
+
+        // the persisted token expired while the session only exists in memory
+        let data = json!({ "a": 1, "token": "secret", EXPIRY_KEY: { "token": 1 } });
+        backend.save(&serde_json::to_vec(&data).unwrap()).unwrap();
+        store.reload().unwrap();
+
+        assert_eq!(store.get("token"), None);
+        assert_eq!(store.get("session"), Some(json!(1)));
+        let inner = store.store.lock().unwrap();
+        assert!(!inner.expiry.contains_key("token"));
+        assert!(inner.expiry.contains_key("session"));
+        drop(inner);
+        assert_eq!(
+            *changes.lock().unwrap(),
+            vec![json!({ "key": "token", "value": null, "exists": false })]
+        );
+        assert_eq!(*events.lock().unwrap(), vec!["token".to_string()]);
+    }
+
+    #[test]
+    fn wrong_encryption_key_fails() {
+        let app = app();
+        let path = std::env::temp_dir().join(format!(
+            "tauri-plugin-store-test-{}-encrypted.json",
+            std::process::id()
+        ));
+        let store = StoreBuilder::new(&app, &path)
+            .encryption_key([7; 32])
+            .create_new()
+            .build()
+            .unwrap();
+        store.set("token", "secret").unwrap();
+        store.save().unwrap();
+        store.close_resource();
+        drop(store);
+        let data = std::fs::read(&path).unwrap();
+
+        let result = StoreBuilder::new(&app, &path)
+            .encryption_key([8; 32])
+            .build();
+        assert!(matches!(result, Err(crate::Error::Decryption(_))));
+        assert_eq!(std::fs::read(&path).unwrap(), data);
+
+        let store = StoreBuilder::new(&app, &path)
+            .encryption_key([7; 32])
+            .build()
+            .unwrap();
+        assert_eq!(store.get("token"), Some(json!("secret")));
+        store.close_resource();
+        let _ = std::fs::remove_file(&path);
+    }
+}
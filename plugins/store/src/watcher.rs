@@ -0,0 +1,84 @@
+// Copyright 2019-2023 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::store::{AutoSave, StoreInner};
+use notify::{Config, Event, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::Value as JsonValue;
+use std::{
+    collections::HashMap,
+    fs,
+    path::Path,
+    sync::{mpsc::channel, Arc, Mutex},
+    thread::spawn,
+};
+use tauri::Runtime;
+
+type Cache = HashMap<String, JsonValue>;
+type ResolveFn = dyn Fn(&Cache, &Cache) -> Cache + Send + Sync;
+
+/// Decides which state is kept when the store file is modified externally
+/// while the store has unsaved changes.
+#[derive(Clone, Default)]
+pub enum ConflictPolicy {
+    /// Discard the unsaved changes and use the state on disk.
+    #[default]
+    DiskWins,
+    /// Keep the keys changed in memory since the last save on top of the state on disk,
+    /// they are written to the file on the next save.
+    MemoryWins,
+    /// Merge the in-memory state (first argument) and the state on disk (second argument).
+    Callback(Arc<ResolveFn>),
+}
+
+impl std::fmt::Debug for ConflictPolicy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::DiskWins => f.write_str("DiskWins"),
+            Self::MemoryWins => f.write_str("MemoryWins"),
+            Self::Callback(_) => f.write_str("Callback"),
+        }
+    }
+}
+
+/// Watches the store file at `path` and reloads `store` when it changes.
+///
+/// The parent directory is watched since saving replaces the file.
+/// If unsaved changes are kept by the [`ConflictPolicy`], `auto_save` is triggered.
+/// The watch stops when the returned watcher is dropped.
+pub(crate) fn watch<R: Runtime>(
+    path: &Path,
+    store: Arc<Mutex<StoreInner<R>>>,
+    auto_save: AutoSave,
+) -> crate::Result<RecommendedWatcher> {
+    let dir = path.parent().expect("invalid store path");
+    fs::create_dir_all(dir)?;
+
+    let (tx, rx) = channel();
+    let mut watcher = RecommendedWatcher::new(tx, Config::default())?;
+    watcher.watch(dir, RecursiveMode::NonRecursive)?;
+
+    let path = path.to_path_buf();
+    spawn(move || {
+        while let Ok(event) = rx.recv() {
+            match event {
+                Ok(Event { kind, paths, .. }) if !kind.is_access() && paths.contains(&path) => {
+                    let reloaded = store.lock().unwrap().reload_external();
+                    match reloaded {
+                        Ok(true) => {
+                            if let Err(e) = auto_save.trigger(&store) {
+                                log::warn!("failed to save store {path:?}: {e}");
+                            }
+                        }
+                        Ok(false) => {}
+                        Err(e) => log::warn!("failed to reload store {path:?}: {e}"),
+                    }
+                }
+                Err(e) => log::error!("failed to watch store {path:?}: {e}"),
+                _ => {}
+            }
+        }
+    });
+
+    Ok(watcher)
+}